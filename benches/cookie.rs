@@ -0,0 +1,161 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Algorithm, Client, Secret, Server, ServerCookieRef, Timing, Version};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+const IPV6: Vector = test_vectors::IPV6_ROLLED_OVER_SECRET;
+const CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203));
+const CLIENT_SECRET: Secret = Secret::new([1; 16]);
+
+fn server(vector: &Vector) -> Server {
+    Server::new(
+        Version::One,
+        [0; 3],
+        vector.timestamp as i64,
+        vector.client_cookie,
+        vector.client_ip,
+        &vector.server_secret,
+    )
+}
+
+fn client(c: &mut Criterion) {
+    let algorithms = [
+        ("siphash24", Algorithm::SipHash24),
+        ("fnv", Algorithm::Fnv),
+        #[cfg(feature = "hmac-sha256")]
+        ("hmac-sha256", Algorithm::HmacSha256),
+    ];
+    let mut group = c.benchmark_group("client/new");
+    for &(name, algorithm) in &algorithms {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                Client::new(
+                    Version::One,
+                    algorithm,
+                    black_box(CLIENT_IP),
+                    black_box(SERVER_IP),
+                    &CLIENT_SECRET,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn server_new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("server/new");
+    for (name, vector) in [("ipv4", &VECTOR), ("ipv6", &IPV6)] {
+        group.bench_function(name, |b| b.iter(|| server(black_box(vector))));
+    }
+    group.finish();
+}
+
+fn regenerate(c: &mut Criterion) {
+    let cookie = server(&VECTOR);
+    let mut group = c.benchmark_group("server/regenerate");
+    for (name, now) in [
+        ("fresh", VECTOR.timestamp + 60),
+        ("stale", VECTOR.timestamp + 1800),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(cookie).regenerate(now as i64, &VECTOR.server_secret))
+        });
+    }
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let now = VECTOR.timestamp as i64;
+    let secrets = [
+        Secret::new([1; 16]),
+        Secret::new([2; 16]),
+        VECTOR.server_secret,
+    ];
+    let mut group = c.benchmark_group("server/decode");
+    for len in 1..=secrets.len() {
+        // The matching secret comes last so every secret is tried
+        let secrets = &secrets[secrets.len() - len..];
+        group.bench_with_input(BenchmarkId::new("secrets", len), secrets, |b, secrets| {
+            b.iter(|| {
+                Server::decode(
+                    now,
+                    VECTOR.client_cookie,
+                    VECTOR.client_ip,
+                    black_box(&VECTOR.server_cookie),
+                    secrets,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn validate(c: &mut Criterion) {
+    let timing = Timing::new();
+    let now = VECTOR.timestamp as i64;
+    let mut group = c.benchmark_group("server/validate");
+    group.bench_function("valid", |b| {
+        b.iter(|| {
+            ServerCookieRef::new(black_box(&VECTOR.server_cookie)).and_then(|cookie| {
+                cookie.validate(
+                    &timing,
+                    now,
+                    VECTOR.client_cookie,
+                    VECTOR.client_ip,
+                    &[VECTOR.server_secret],
+                )
+            })
+        })
+    });
+    group.finish();
+}
+
+fn decode_failure(c: &mut Criterion) {
+    let mut invalid_hash = VECTOR.server_cookie;
+    invalid_hash[15] ^= 1;
+    let failures: [(&str, i64, &[u8]); 3] = [
+        (
+            "incorrect_length",
+            VECTOR.timestamp as i64,
+            &VECTOR.server_cookie[..15],
+        ),
+        (
+            "expired",
+            (VECTOR.timestamp + 3601) as i64,
+            &VECTOR.server_cookie,
+        ),
+        ("invalid_hash", VECTOR.timestamp as i64, &invalid_hash),
+    ];
+    let mut group = c.benchmark_group("server/decode/failure");
+    for (name, now, server_cookie) in failures {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let result = Server::decode(
+                    now,
+                    VECTOR.client_cookie,
+                    VECTOR.client_ip,
+                    black_box(server_cookie),
+                    &[VECTOR.server_secret],
+                );
+                assert!(result.is_err());
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    client,
+    server_new,
+    regenerate,
+    decode,
+    validate,
+    decode_failure
+);
+criterion_main!(benches);
@@ -0,0 +1,209 @@
+//! Server Cookies as constructed by BIND with `cookie-algorithm aes`
+//!
+//! BIND predates [RFC9018] and lays its cookies out as a 32-bit nonce, the
+//! 32-bit timestamp and a 64-bit hash. There is no version or algorithm byte, so
+//! these cookies are not described by [`Algorithm`](crate::Algorithm) and cannot
+//! be validated by RFC9018 implementations. Only use this module to interoperate
+//! with BIND servers that still emit them.
+//!
+//! The hash is built with AES-128 keyed with the secret, following
+//! `compute_cookie` in BIND's `lib/ns/client.c`. The client cookie, nonce and
+//! timestamp are encrypted as one block and its halves are folded together by
+//! XOR. That half is encrypted with the client address in a second block, an
+//! IPv4 address padded with zeros. An IPv6 address takes two blocks, its first
+//! half folded in before its second half is added. The halves of the last block
+//! are folded together into the hash.
+//!
+//! [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018
+
+use crate::{check_timestamp, secs, Clock, Error, IpAddr, Secret, Timing};
+use crate::{CLIENT_COOKIE_LEN, SERVER_COOKIE_LEN};
+use ::aes::cipher::generic_array::GenericArray;
+use ::aes::cipher::{BlockEncrypt, KeyInit};
+use ::aes::Aes128;
+use core::convert::TryFrom;
+use subtle::ConstantTimeEq;
+
+const BLOCK_LEN: usize = 16;
+const HASH_LEN: usize = 8;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+struct Data {
+    nonce: u32,
+    time: i64,
+    client_cookie: [u8; CLIENT_COOKIE_LEN],
+    client_ip: IpAddr,
+}
+
+impl Data {
+    fn hash(&self, server_secret: &Secret) -> [u8; HASH_LEN] {
+        let cipher = Aes128::new(GenericArray::from_slice(server_secret.as_bytes()));
+        let encrypt = |first: [u8; HASH_LEN], second: &[u8]| {
+            let mut block = [0; BLOCK_LEN];
+            block[..HASH_LEN].copy_from_slice(&first);
+            block[HASH_LEN..HASH_LEN + second.len()].copy_from_slice(second);
+            cipher.encrypt_block(GenericArray::from_mut_slice(&mut block));
+            fold(block)
+        };
+        let mut timestamp = [0; HASH_LEN];
+        timestamp[..4].copy_from_slice(&self.nonce.to_be_bytes());
+        timestamp[4..].copy_from_slice(&(self.time as u32).to_be_bytes());
+        let hash = encrypt(self.client_cookie, &timestamp);
+        match self.client_ip {
+            IpAddr::V4(ip) => encrypt(hash, &ip.octets()),
+            IpAddr::V6(ip) => {
+                let octets = ip.octets();
+                let hash = encrypt(hash, &octets[..HASH_LEN]);
+                encrypt(hash, &octets[HASH_LEN..])
+            }
+        }
+    }
+}
+
+/// Folds an AES block into 64 bits by XORing its halves
+fn fold(block: [u8; BLOCK_LEN]) -> [u8; HASH_LEN] {
+    let mut hash = [0; HASH_LEN];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = block[i] ^ block[i + HASH_LEN];
+    }
+    hash
+}
+
+/// A 128-bit Server Cookie as constructed by BIND with AES
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[must_use]
+pub struct Server {
+    data: Data,
+    hash: [u8; HASH_LEN],
+}
+
+impl Server {
+    /// Creates a new server cookie bound to the address of the client it is issued to
+    ///
+    /// BIND picks a random `nonce` for every cookie it creates.
+    pub fn new(
+        nonce: u32,
+        time: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_secret: &impl AsRef<Secret>,
+    ) -> Self {
+        let data = Data {
+            nonce,
+            client_cookie,
+            client_ip,
+            time: time.now(),
+        };
+        Self {
+            data,
+            hash: data.hash(server_secret.as_ref()),
+        }
+    }
+
+    /// Regenerates a server cookie if the current cookie is more than 30 minutes old
+    ///
+    /// The nonce of the cookie is kept, pass a new one to [`Server::new`] to change it.
+    pub fn regenerate(self, time: impl Clock, server_secret: &impl AsRef<Secret>) -> Self {
+        self.regenerate_with(&Timing::new(), time, server_secret)
+    }
+
+    /// Regenerates a server cookie if the current cookie is older than the refresh age of `timing`
+    pub fn regenerate_with(
+        mut self,
+        timing: &Timing,
+        time: impl Clock,
+        server_secret: &impl AsRef<Secret>,
+    ) -> Self {
+        let time = time.now();
+        if self.data.time > time.saturating_sub(secs(timing.refresh)) {
+            return self;
+        }
+        self.data.time = time;
+        self.hash = self.data.hash(server_secret.as_ref());
+        self
+    }
+
+    /// Creates and validates a server cookie from bytes
+    ///
+    /// The cookie is returned along with the index of the secret in `server_secrets`
+    /// that validated it.
+    ///
+    /// `client_ip` must be the source address of the query carrying the cookie.
+    pub fn decode(
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_cookie: &[u8],
+        server_secrets: &[impl AsRef<Secret>],
+    ) -> Result<(Self, usize), Error> {
+        Self::decode_with(
+            &Timing::new(),
+            now,
+            client_cookie,
+            client_ip,
+            server_cookie,
+            server_secrets,
+        )
+    }
+
+    /// Creates and validates a server cookie from bytes within the validity window of `timing`
+    pub fn decode_with(
+        timing: &Timing,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_cookie: &[u8],
+        server_secrets: &[impl AsRef<Secret>],
+    ) -> Result<(Self, usize), Error> {
+        let bytes = <&[u8; SERVER_COOKIE_LEN]>::try_from(server_cookie)
+            .map_err(|_| Error::IncorrectLength(server_cookie.len()))?;
+        let nonce = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let timestamp = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        let time = check_timestamp(timing, now.now(), timestamp)?;
+        for (index, secret) in server_secrets.iter().enumerate() {
+            let cookie = Self::new(nonce, time, client_cookie, client_ip, secret);
+            if cookie.verify(server_cookie) {
+                return Ok((cookie, index));
+            }
+        }
+        Err(Error::InvalidHash)
+    }
+
+    /// Checks in constant time that `server_cookie` holds the bytes of this server cookie
+    #[must_use]
+    pub fn verify(&self, server_cookie: &[u8]) -> bool {
+        self.encode()[..].ct_eq(server_cookie).into()
+    }
+
+    /// Returns the nonce of the cookie
+    #[must_use]
+    pub const fn nonce(&self) -> u32 {
+        self.data.nonce
+    }
+
+    /// Converts a server cookie to bytes
+    #[must_use]
+    pub const fn encode(self) -> [u8; SERVER_COOKIE_LEN] {
+        let nonce = self.data.nonce.to_be_bytes();
+        let timestamp = (self.data.time as u32).to_be_bytes();
+        let hash = self.hash;
+        [
+            nonce[0],
+            nonce[1],
+            nonce[2],
+            nonce[3],
+            timestamp[0],
+            timestamp[1],
+            timestamp[2],
+            timestamp[3],
+            hash[0],
+            hash[1],
+            hash[2],
+            hash[3],
+            hash[4],
+            hash[5],
+            hash[6],
+            hash[7],
+        ]
+    }
+}
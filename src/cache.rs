@@ -0,0 +1,111 @@
+use crate::{Client, CookieOption, IpAddr};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct Entry {
+    server_ip: IpAddr,
+    option: CookieOption,
+    updated: u64,
+}
+
+/// Remembers the Server Cookies learned from up to `N` servers
+///
+/// As prescribed by [RFC7873] a Server Cookie is only echoed back together with
+/// the Client Cookie it was learned with. When the cache is full, the server
+/// that was updated the longest time ago is evicted.
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#section-5.3
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct ClientCache<const N: usize> {
+    entries: [Option<Entry>; N],
+    updates: u64,
+}
+
+impl<const N: usize> ClientCache<N> {
+    /// Creates an empty cache
+    pub const fn new() -> Self {
+        Self {
+            entries: [None; N],
+            updates: 0,
+        }
+    }
+
+    /// Returns the COOKIE option last learned from a server
+    #[must_use]
+    pub fn get(&self, server_ip: IpAddr) -> Option<&CookieOption> {
+        self.entries
+            .iter()
+            .flatten()
+            .find(|entry| entry.server_ip == server_ip)
+            .map(|entry| &entry.option)
+    }
+
+    /// Returns the COOKIE option to send to a server
+    ///
+    /// The option only carries a Server Cookie if one was learned from the
+    /// server with the same Client Cookie.
+    pub fn option(&self, server_ip: IpAddr, client: Client) -> CookieOption {
+        match self.get(server_ip) {
+            Some(option) if client.verify(&option.client_cookie()) => *option,
+            _ => CookieOption::from_cookies(client, None),
+        }
+    }
+
+    /// Remembers the COOKIE option of a response from a server
+    ///
+    /// Options without a Server Cookie are ignored.
+    pub fn update(&mut self, server_ip: IpAddr, option: CookieOption) {
+        if option.server_cookie().is_none() {
+            return;
+        }
+        self.updates += 1;
+        let entry = Entry {
+            server_ip,
+            option,
+            updated: self.updates,
+        };
+        let index = self
+            .position(server_ip)
+            .or_else(|| self.entries.iter().position(Option::is_none))
+            .or_else(|| (0..N).min_by_key(|&index| self.entries[index].map(|entry| entry.updated)));
+        if let Some(index) = index {
+            self.entries[index] = Some(entry);
+        }
+    }
+
+    /// Forgets the Server Cookie of a server
+    pub fn remove(&mut self, server_ip: IpAddr) -> Option<CookieOption> {
+        let index = self.position(server_ip)?;
+        self.entries[index].take().map(|entry| entry.option)
+    }
+
+    /// Forgets all Server Cookies
+    pub fn clear(&mut self) {
+        self.entries = [None; N];
+    }
+
+    /// Returns the number of servers in the cache
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    /// Returns `true` if the cache holds no servers
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn position(&self, server_ip: IpAddr) -> Option<usize> {
+        self.entries.iter().position(|slot| match slot {
+            Some(entry) => entry.server_ip == server_ip,
+            None => false,
+        })
+    }
+}
+
+impl<const N: usize> Default for ClientCache<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
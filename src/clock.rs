@@ -0,0 +1,106 @@
+use core::cell::Cell;
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
+
+/// Tells the time in Unix seconds, the only resolution cookies need
+///
+/// It is implemented by the Unix seconds themselves and, behind the `time`
+/// feature, by `OffsetDateTime`, which both stand for a fixed time, and by the
+/// clocks of this module.
+///
+/// Implement it on top of a coarse clock, such as one refreshed once a second,
+/// to avoid reading the system clock for every packet.
+pub trait Clock {
+    /// Returns the current Unix time in seconds
+    fn now(&self) -> i64;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> i64 {
+        (**self).now()
+    }
+}
+
+impl Clock for i64 {
+    fn now(&self) -> i64 {
+        *self
+    }
+}
+
+#[cfg(feature = "time")]
+impl Clock for OffsetDateTime {
+    fn now(&self) -> i64 {
+        self.unix_timestamp()
+    }
+}
+
+/// Reads the time from the system clock
+#[cfg(not(feature = "no-std-net"))]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[must_use]
+pub struct SystemClock;
+
+#[cfg(not(feature = "no-std-net"))]
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(error) => -(error.duration().as_secs() as i64),
+        }
+    }
+}
+
+/// A clock which always tells the same time
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[must_use]
+pub struct FixedClock(i64);
+
+impl FixedClock {
+    /// Creates a clock stopped at `now` Unix seconds
+    pub const fn new(now: i64) -> Self {
+        Self(now)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.0
+    }
+}
+
+/// A clock which only moves when told to, to drive time in tests
+///
+/// It can be moved while it is borrowed, so the same clock can be handed to
+/// several parts of a program.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+#[must_use]
+pub struct ManualClock {
+    now: Cell<i64>,
+}
+
+impl ManualClock {
+    /// Creates a clock set to `now` Unix seconds
+    pub const fn new(now: i64) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    /// Sets the clock to `now` Unix seconds
+    pub fn set(&self, now: i64) {
+        self.now.set(now);
+    }
+
+    /// Moves the clock by `seconds`, backwards if negative
+    pub fn advance(&self, seconds: i64) {
+        self.now.set(self.now.get() + seconds);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> i64 {
+        self.now.get()
+    }
+}
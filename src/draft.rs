@@ -0,0 +1,197 @@
+//! Server Cookies as laid out by [draft-sury-toorop-dnsop-server-cookies]
+//!
+//! The draft carries an Algorithm byte followed by a 16-bit Reserved field
+//! where [RFC9018] has a 24-bit Reserved field. Cookies in this layout cannot
+//! be validated by RFC9018 implementations, so only use this module to
+//! interoperate with servers that still emit it.
+//!
+//! Besides SipHash-2-4, the hash can be calculated with the FNV and, behind the
+//! `hmac-sha256` feature, HMAC-SHA-256-64 algorithms of [RFC7873]. FNV is not
+//! keyed so the secret is hashed after the other fields.
+//!
+//! [draft-sury-toorop-dnsop-server-cookies]: https://datatracker.ietf.org/doc/html/draft-sury-toorop-dns-cookies-algorithms-00
+//! [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018
+//! [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#appendix-B
+
+use crate::mac::Message;
+use crate::{check_timestamp, secs, Algorithm, Clock, Error, IpAddr, Secret, Timing, Version};
+use crate::{CLIENT_COOKIE_LEN, SERVER_COOKIE_LEN};
+use core::convert::TryFrom;
+use subtle::ConstantTimeEq;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+struct Data {
+    version: Version,
+    algorithm: Algorithm,
+    reserved: u16,
+    time: i64,
+    client_cookie: [u8; CLIENT_COOKIE_LEN],
+    client_ip: IpAddr,
+}
+
+impl Data {
+    fn hash(&self, server_secret: &Secret) -> u64 {
+        match self.version {
+            Version::One => {
+                let mut message = Message::new();
+                message.write(&self.client_cookie);
+                message.write(&[self.version as u8, self.algorithm as u8]);
+                message.write(&self.reserved.to_be_bytes());
+                message.write(&(self.time as u32).to_be_bytes());
+                message.write_ip(self.client_ip);
+                u64::from_le_bytes(self.algorithm.mac(server_secret, message.as_bytes()))
+            }
+        }
+    }
+}
+
+/// A 128-bit Server Cookie as laid out by the draft
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[must_use]
+pub struct Server {
+    data: Data,
+    hash: u64,
+}
+
+impl Server {
+    /// Creates a new server cookie bound to the address of the client it is issued to
+    pub fn new(
+        version: Version,
+        algorithm: Algorithm,
+        reserved: u16,
+        time: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_secret: &impl AsRef<Secret>,
+    ) -> Self {
+        let data = Data {
+            version,
+            algorithm,
+            reserved,
+            client_cookie,
+            client_ip,
+            time: time.now(),
+        };
+        Self {
+            data,
+            hash: data.hash(server_secret.as_ref()),
+        }
+    }
+
+    /// Regenerates a server cookie if the current cookie is more than 30 minutes old
+    /// as prescribed by the draft
+    pub fn regenerate(self, time: impl Clock, server_secret: &impl AsRef<Secret>) -> Self {
+        self.regenerate_with(&Timing::new(), time, server_secret)
+    }
+
+    /// Regenerates a server cookie if the current cookie is older than the refresh age of `timing`
+    pub fn regenerate_with(
+        mut self,
+        timing: &Timing,
+        time: impl Clock,
+        server_secret: &impl AsRef<Secret>,
+    ) -> Self {
+        let time = time.now();
+        if self.data.time > time.saturating_sub(secs(timing.refresh)) {
+            return self;
+        }
+        self.data.time = time;
+        self.hash = self.data.hash(server_secret.as_ref());
+        self
+    }
+
+    /// Creates and validates a server cookie from bytes
+    ///
+    /// The cookie is returned along with the index of the secret in `server_secrets`
+    /// that validated it.
+    ///
+    /// `client_ip` must be the source address of the query carrying the cookie.
+    pub fn decode(
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_cookie: &[u8],
+        server_secrets: &[impl AsRef<Secret>],
+    ) -> Result<(Self, usize), Error> {
+        Self::decode_with(
+            &Timing::new(),
+            now,
+            client_cookie,
+            client_ip,
+            server_cookie,
+            server_secrets,
+        )
+    }
+
+    /// Creates and validates a server cookie from bytes within the validity window of `timing`
+    pub fn decode_with(
+        timing: &Timing,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_cookie: &[u8],
+        server_secrets: &[impl AsRef<Secret>],
+    ) -> Result<(Self, usize), Error> {
+        let cookie_len = server_cookie.len();
+        if cookie_len != SERVER_COOKIE_LEN {
+            return Err(Error::IncorrectLength(cookie_len));
+        }
+        let version = Version::try_from(server_cookie[0])?;
+        let algorithm = Algorithm::try_from(server_cookie[1])?;
+        let reserved = u16::from_be_bytes([server_cookie[2], server_cookie[3]]);
+        let timestamp = u32::from_be_bytes([
+            server_cookie[4],
+            server_cookie[5],
+            server_cookie[6],
+            server_cookie[7],
+        ]);
+        let time = check_timestamp(timing, now.now(), timestamp)?;
+        for (index, secret) in server_secrets.iter().enumerate() {
+            let cookie = Self::new(
+                version,
+                algorithm,
+                reserved,
+                time,
+                client_cookie,
+                client_ip,
+                secret,
+            );
+            if cookie.verify(server_cookie) {
+                return Ok((cookie, index));
+            }
+        }
+        Err(Error::InvalidHash)
+    }
+
+    /// Checks in constant time that `server_cookie` holds the bytes of this server cookie
+    #[must_use]
+    pub fn verify(&self, server_cookie: &[u8]) -> bool {
+        self.encode()[..].ct_eq(server_cookie).into()
+    }
+
+    /// Converts a server cookie to bytes
+    #[must_use]
+    pub const fn encode(self) -> [u8; SERVER_COOKIE_LEN] {
+        let reserved = self.data.reserved.to_be_bytes();
+        let timestamp = (self.data.time as u32).to_be_bytes();
+        let hash = self.hash.to_le_bytes();
+        [
+            self.data.version as u8,
+            self.data.algorithm as u8,
+            reserved[0],
+            reserved[1],
+            timestamp[0],
+            timestamp[1],
+            timestamp[2],
+            timestamp[3],
+            hash[0],
+            hash[1],
+            hash[2],
+            hash[3],
+            hash[4],
+            hash[5],
+            hash[6],
+            hash[7],
+        ]
+    }
+}
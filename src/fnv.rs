@@ -0,0 +1,33 @@
+use core::hash::Hasher;
+
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hash function used by [RFC7873] Appendix A.1
+///
+/// FNV is not keyed, the secret is hashed as part of the message. RFC7873 does
+/// not say in which order the bytes of the hash go on the wire, they are put
+/// in little-endian order like those of SipHash-2-4.
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#appendix-A.1
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub(crate) struct Fnv64(u64);
+
+impl Fnv64 {
+    pub(crate) const fn new() -> Self {
+        Self(OFFSET_BASIS)
+    }
+}
+
+impl Hasher for Fnv64 {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{secs, Algorithm, Client, Clock, Secret, Version};
+use core::time::Duration;
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Derives Client Cookies from a client secret which it regenerates on a schedule
+///
+/// As recommended by [RFC9018], cookies are derived per server and from the
+/// client address when it is known, so that they change along with it and
+/// cannot be linked across servers. When the source address is only picked
+/// once the query is sent, the unspecified address of the family of the server
+/// stands in for it.
+///
+/// The secret is a [`Secret`] unless told otherwise, use a [`ClientSecret`] to
+/// have it zeroized once it is replaced.
+///
+/// [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018#section-3
+/// [`ClientSecret`]: crate::ClientSecret
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct ClientCookieGenerator<S = Secret> {
+    secret: S,
+    created: i64,
+    lifetime: Duration,
+    algorithm: Algorithm,
+}
+
+impl<S: AsRef<Secret>> ClientCookieGenerator<S> {
+    /// Creates a generator which replaces its secret daily and derives cookies with SipHash-2-4
+    pub fn new(now: impl Clock, secret: S) -> Self {
+        Self {
+            secret,
+            created: now.now(),
+            lifetime: Duration::from_secs(24 * 60 * 60),
+            algorithm: Algorithm::SipHash24,
+        }
+    }
+
+    /// Sets how long a secret is used before it is due for replacement
+    pub const fn lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets the algorithm the cookies are derived with
+    pub const fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Returns `true` if the secret has outlived its lifetime
+    #[must_use]
+    pub fn is_due(&self, now: impl Clock) -> bool {
+        self.created.saturating_add(secs(self.lifetime)) <= now.now()
+    }
+
+    /// Replaces the secret
+    ///
+    /// Every Server Cookie learned so far becomes useless, as the Client
+    /// Cookies it was learned with are no longer sent.
+    pub fn rotate(&mut self, now: impl Clock, secret: S) {
+        self.secret = secret;
+        self.created = now.now();
+    }
+
+    /// Replaces the secret with a fresh one if it is due, returning whether it was replaced
+    pub fn refresh(&mut self, now: impl Clock, secret: impl FnOnce() -> S) -> bool {
+        let now = now.now();
+        if !self.is_due(now) {
+            return false;
+        }
+        self.rotate(now, secret());
+        true
+    }
+
+    /// Derives the Client Cookie to send to a server
+    pub fn cookie(&self, client_ip: Option<IpAddr>, server_ip: IpAddr) -> Client {
+        let client_ip = client_ip.unwrap_or(match server_ip {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0)),
+        });
+        Client::new(
+            Version::One,
+            self.algorithm,
+            client_ip,
+            server_ip,
+            &self.secret,
+        )
+    }
+}
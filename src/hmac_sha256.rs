@@ -0,0 +1,31 @@
+use core::hash::Hasher;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// HMAC-SHA-256 truncated to 64 bits as used by [RFC7873] Appendix A.2
+///
+/// The hash is the first 8 bytes of the MAC, read in the byte order the
+/// cookies of this crate are encoded in.
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#appendix-A.2
+#[derive(Clone)]
+pub(crate) struct HmacSha256(Hmac<Sha256>);
+
+impl HmacSha256 {
+    pub(crate) fn new_with_key(key: &[u8]) -> Self {
+        Self(Hmac::new_from_slice(key).expect("HMAC accepts keys of any length"))
+    }
+}
+
+impl Hasher for HmacSha256 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mac = self.0.clone().finalize().into_bytes();
+        let mut hash = [0; 8];
+        hash.copy_from_slice(&mac[..8]);
+        u64::from_le_bytes(hash)
+    }
+}
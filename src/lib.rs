@@ -5,9 +5,20 @@
 //! Server Cookie constructed by one implementation cannot be validated
 //! by another.
 //!
-//! This crate is an implementation of [draft-sury-toorop-dnsop-server-cookies] which provides precise
+//! This crate is an implementation of [RFC9018] which provides precise
 //! directions for creating Server and Client Cookies to address this issue.
 //!
+//! The layout of the earlier [draft-sury-toorop-dnsop-server-cookies], which
+//! carries an Algorithm byte, is available in the `draft` module behind the
+//! `draft-00` feature.
+//!
+//! The AES Server Cookies of BIND, which predate both, are available in the
+//! `aes` module behind the `aes` feature.
+//!
+//! The time is taken as Unix seconds or from a [`Clock`]. The `time` feature
+//! lets an `OffsetDateTime` of the `time` crate stand in for it.
+//!
+//! [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018
 //! [draft-sury-toorop-dnsop-server-cookies]: https://datatracker.ietf.org/doc/html/draft-sury-toorop-dns-cookies-algorithms-00
 
 #![cfg_attr(feature = "no-std-net", no_std)]
@@ -15,17 +26,97 @@
 
 use core::convert::TryFrom;
 use core::fmt;
-use core::hash::Hasher;
+use core::time::Duration;
+use mac::Message;
 #[cfg(feature = "no-std-net")]
 use no_std_net::IpAddr;
-use siphasher::sip::SipHasher24;
 #[cfg(not(feature = "no-std-net"))]
 use std::net::IpAddr;
-use time::ext::NumericalDuration;
-use time::{OffsetDateTime, UtcOffset};
+use subtle::ConstantTimeEq;
+
+#[cfg(feature = "aes")]
+pub mod aes;
+mod cache;
+mod clock;
+#[cfg(feature = "draft-00")]
+pub mod draft;
+mod fnv;
+mod generator;
+#[cfg(feature = "hmac-sha256")]
+mod hmac_sha256;
+mod mac;
+mod option;
+mod policy;
+mod retry;
+mod ring;
+mod secret;
+mod server_ref;
+pub mod test_vectors;
+
+pub use cache::ClientCache;
+#[cfg(not(feature = "no-std-net"))]
+pub use clock::SystemClock;
+pub use clock::{Clock, FixedClock, ManualClock};
+pub use generator::ClientCookieGenerator;
+pub use mac::CookieMac;
+pub use option::CookieOption;
+pub use policy::{ClientAction, ClientPolicy, ServerAction, ServerPolicy, BADCOOKIE};
+pub use retry::{BadCookieRetry, Retry};
+pub use ring::{SecretRing, SecretRole};
+pub use secret::{ClientSecret, ServerSecret};
+pub use server_ref::ServerCookieRef;
 
 const SERVER_COOKIE_LEN: usize = 16;
 const CLIENT_COOKIE_LEN: usize = 8;
+const SECRET_LEN: usize = 16;
+/// How far apart serial number arithmetic can tell two timestamps
+const SERIAL_RANGE: i64 = 1 << 31;
+
+/// A 128-bit secret used as the SipHash-2-4 key
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[must_use]
+pub struct Secret([u8; SECRET_LEN]);
+
+impl Secret {
+    /// Creates a new secret
+    pub const fn new(secret: [u8; SECRET_LEN]) -> Self {
+        Self(secret)
+    }
+
+    /// Returns the key material of the secret
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; SECRET_LEN] {
+        &self.0
+    }
+}
+
+impl From<[u8; SECRET_LEN]> for Secret {
+    fn from(secret: [u8; SECRET_LEN]) -> Self {
+        Self(secret)
+    }
+}
+
+impl AsRef<Secret> for Secret {
+    fn as_ref(&self) -> &Secret {
+        self
+    }
+}
+
+impl TryFrom<&[u8]> for Secret {
+    type Error = Error;
+
+    fn try_from(secret: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; SECRET_LEN]>::try_from(secret)
+            .map(Self)
+            .map_err(|_| Error::SecretLength(secret.len()))
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
 
 /// Prescribes the structure and Hash calculation formula
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
@@ -48,7 +139,15 @@ impl TryFrom<u8> for Version {
 /// Defines what algorithm function to use for calculating the Hash
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 #[must_use]
+#[non_exhaustive]
 pub enum Algorithm {
+    /// FNV-1a 64 as described in RFC7873 Appendix A.1, with the secret hashed
+    /// after the message and the hash in little-endian order
+    Fnv = 1,
+    /// HMAC-SHA-256-64 as described in RFC7873 Appendix A.2
+    #[cfg(feature = "hmac-sha256")]
+    HmacSha256 = 2,
+    /// SipHash-2-4 as prescribed by RFC9018
     SipHash24 = 4,
 }
 
@@ -58,43 +157,101 @@ impl TryFrom<u8> for Algorithm {
     fn try_from(algorithm: u8) -> Result<Self, Self::Error> {
         match algorithm {
             v if Algorithm::SipHash24 as u8 == v => Ok(Algorithm::SipHash24),
-            1 => Err(Error::UnsupportedAlgorithm("FNV")),
+            v if Algorithm::Fnv as u8 == v => Ok(Algorithm::Fnv),
+            #[cfg(feature = "hmac-sha256")]
+            v if Algorithm::HmacSha256 as u8 == v => Ok(Algorithm::HmacSha256),
+            #[cfg(not(feature = "hmac-sha256"))]
             2 => Err(Error::UnsupportedAlgorithm("HMAC-SHA-256-64")),
+            // BIND's AES cookies carry no algorithm byte, see the `aes` module
             3 => Err(Error::UnsupportedAlgorithm("AES")),
             v => Err(Error::UnknownAlgorithm(v)),
         }
     }
 }
 
+/// How long server cookies are valid and when they are regenerated
+///
+/// The defaults are the ones recommended by [RFC9018]: cookies are valid for an
+/// hour, up to 5 minutes in the future, and are regenerated once they are more
+/// than 30 minutes old.
+///
+/// [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018#section-4.3
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[must_use]
+pub struct Timing {
+    lifetime: Duration,
+    refresh: Duration,
+    skew: Duration,
+}
+
+impl Timing {
+    /// Creates the timing recommended by the RFC
+    pub const fn new() -> Self {
+        Self {
+            lifetime: Duration::from_secs(60 * 60),
+            refresh: Duration::from_secs(30 * 60),
+            skew: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// Sets how long after its creation a cookie is valid
+    ///
+    /// The timestamp can only tell apart times up to 2^31 seconds, about 68
+    /// years, from now, so longer lifetimes are taken to be that long.
+    pub const fn lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets how old a cookie gets before it is regenerated
+    pub const fn refresh(mut self, refresh: Duration) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Sets how far in the future a cookie may have been created
+    ///
+    /// Like the lifetime, the skew is capped at 2^31 seconds.
+    pub const fn skew(mut self, skew: Duration) -> Self {
+        self.skew = skew;
+        self
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 struct Data {
     version: Version,
-    algorithm: Algorithm,
-    reserved: u16,
-    time: OffsetDateTime,
+    reserved: [u8; 3],
+    time: i64,
     client_cookie: [u8; CLIENT_COOKIE_LEN],
+    client_ip: IpAddr,
 }
 
 impl Data {
-    fn hash(&self, server_secret: &[u8]) -> u64 {
+    fn hash(&self, server_secret: &impl CookieMac) -> u64 {
         match self.version {
-            Version::One => match self.algorithm {
-                Algorithm::SipHash24 => {
-                    let mut hasher = SipHasher24::new();
-                    hasher.write(&self.client_cookie);
-                    hasher.write_u8(self.version as u8);
-                    hasher.write_u8(self.algorithm as u8);
-                    hasher.write_u16(self.reserved);
-                    hasher.write_u32(self.time.unix_timestamp() as u32);
-                    hasher.write(server_secret);
-                    hasher.finish()
-                }
-            },
+            Version::One => {
+                let mut message = Message::new();
+                message.write(&self.client_cookie);
+                message.write(&[self.version as u8]);
+                message.write(&self.reserved);
+                message.write(&(self.time as u32).to_be_bytes());
+                message.write_ip(self.client_ip);
+                u64::from_le_bytes(server_secret.mac(message.as_bytes()))
+            }
         }
     }
 }
 
-/// A 128-bit Server Cookie
+/// A 128-bit Server Cookie as laid out by [RFC9018]
+///
+/// [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 #[must_use]
 pub struct Server {
@@ -103,21 +260,26 @@ pub struct Server {
 }
 
 impl Server {
-    /// Creates a new server cookie
+    /// Creates a new server cookie bound to the address of the client it is issued to
+    ///
+    /// The RFC requires the reserved field to be generated as zero. A non-zero
+    /// value is only useful when reconstructing a cookie received from a peer.
+    ///
+    /// The Hash is calculated by `server_secret`, SipHash-2-4 for a [`Secret`].
     pub fn new(
         version: Version,
-        algorithm: Algorithm,
-        reserved: u16,
-        time: OffsetDateTime,
+        reserved: [u8; 3],
+        time: impl Clock,
         client_cookie: [u8; CLIENT_COOKIE_LEN],
-        server_secret: &[u8],
+        client_ip: IpAddr,
+        server_secret: &impl CookieMac,
     ) -> Self {
         let data = Data {
             version,
-            algorithm,
             reserved,
             client_cookie,
-            time: time.to_offset(UtcOffset::UTC),
+            client_ip,
+            time: time.now(),
         };
         Self {
             data,
@@ -126,76 +288,97 @@ impl Server {
     }
 
     /// Regenerates a server cookie if the current cookie is more than 30 minutes old
-    /// as prescribed by the draft
-    pub fn regenerate(mut self, time: OffsetDateTime, server_secret: &[u8]) -> Self {
-        let time = time.to_offset(UtcOffset::UTC);
-        if self.data.time > time - 30.minutes() {
+    /// as prescribed by the RFC
+    ///
+    /// The reserved field of a regenerated cookie is reset to zero.
+    pub fn regenerate(self, time: impl Clock, server_secret: &impl CookieMac) -> Self {
+        self.regenerate_with(&Timing::new(), time, server_secret)
+    }
+
+    /// Regenerates a server cookie if the current cookie is older than the refresh age of `timing`
+    pub fn regenerate_with(
+        mut self,
+        timing: &Timing,
+        time: impl Clock,
+        server_secret: &impl CookieMac,
+    ) -> Self {
+        let time = time.now();
+        if self.data.time > time.saturating_sub(secs(timing.refresh)) {
             return self;
         }
+        self.data.reserved = [0; 3];
         self.data.time = time;
         self.hash = self.data.hash(server_secret);
         self
     }
 
     /// Creates and validates a server cookie from bytes
+    ///
+    /// The cookie is returned along with the index of the secret in `server_secrets`
+    /// that validated it.
+    ///
+    /// `client_ip` must be the source address of the query carrying the cookie.
+    ///
+    /// The reserved field is not required to be zero, it is fed into the hash as received.
     pub fn decode(
-        mut now: OffsetDateTime,
+        now: impl Clock,
         client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
         server_cookie: &[u8],
-        server_secrets: &[&[u8]],
-    ) -> Result<Self, Error> {
-        now = now.to_offset(UtcOffset::UTC);
-        let cookie_len = server_cookie.len();
-        if cookie_len != SERVER_COOKIE_LEN {
-            return Err(Error::IncorrectLength(cookie_len));
-        }
-        let version = Version::try_from(server_cookie[0])?;
-        let algorithm = Algorithm::try_from(server_cookie[1])?;
-        let reserved = u16::from_be_bytes([server_cookie[2], server_cookie[3]]);
-        let time = {
-            let timestamp = u32::from_be_bytes([
-                server_cookie[4],
-                server_cookie[5],
-                server_cookie[6],
-                server_cookie[7],
-            ]);
-            OffsetDateTime::from_unix_timestamp(timestamp as i64).map_err(Error::TimestampRange)?
-        };
-        if time < now - 1.hours() {
-            return Err(Error::Expired);
-        } else if time > now + 5.minutes() {
-            return Err(Error::TimeTravellor);
-        }
-        let hash = u64::from_be_bytes([
-            server_cookie[8],
-            server_cookie[9],
-            server_cookie[10],
-            server_cookie[11],
-            server_cookie[12],
-            server_cookie[13],
-            server_cookie[14],
-            server_cookie[15],
-        ]);
-        for secret in server_secrets {
-            let cookie = Self::new(version, algorithm, reserved, time, client_cookie, secret);
-            if cookie.hash == hash {
-                return Ok(cookie);
-            }
-        }
-        Err(Error::InvalidHash)
+        server_secrets: &[impl CookieMac],
+    ) -> Result<(Self, usize), Error> {
+        Self::decode_with(
+            &Timing::new(),
+            now,
+            client_cookie,
+            client_ip,
+            server_cookie,
+            server_secrets,
+        )
+    }
+
+    /// Creates and validates a server cookie from bytes within the validity window of `timing`
+    ///
+    /// See [`ServerCookieRef`] to validate a cookie without decoding it.
+    pub fn decode_with(
+        timing: &Timing,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_cookie: &[u8],
+        server_secrets: &[impl CookieMac],
+    ) -> Result<(Self, usize), Error> {
+        ServerCookieRef::new(server_cookie)?.decode(
+            timing,
+            now,
+            client_cookie,
+            client_ip,
+            server_secrets,
+        )
+    }
+
+    /// Checks that `server_cookie` holds the bytes of this server cookie
+    ///
+    /// The bytes are compared in constant time so how long the check takes does
+    /// not depend on how many of them match, only on the length of `server_cookie`.
+    #[must_use]
+    pub fn verify(&self, server_cookie: &[u8]) -> bool {
+        self.encode()[..].ct_eq(server_cookie).into()
     }
 
     /// Converts a server cookie to bytes
+    ///
+    /// Only the lower 32 bits of the Unix time are kept in the timestamp.
     #[must_use]
     pub const fn encode(self) -> [u8; SERVER_COOKIE_LEN] {
-        let reserved = self.data.reserved.to_be_bytes();
-        let timestamp = (self.data.time.unix_timestamp() as u32).to_be_bytes();
-        let hash = self.hash.to_be_bytes();
+        let reserved = self.data.reserved;
+        let timestamp = (self.data.time as u32).to_be_bytes();
+        let hash = self.hash.to_le_bytes();
         [
             self.data.version as u8,
-            self.data.algorithm as u8,
             reserved[0],
             reserved[1],
+            reserved[2],
             timestamp[0],
             timestamp[1],
             timestamp[2],
@@ -212,6 +395,31 @@ impl Server {
     }
 }
 
+/// Checks that a timestamp is within the validity window at `now` Unix seconds
+///
+/// The timestamp only holds the lower 32 bits of the Unix time so, as required by
+/// [RFC9018], it is compared to `now` using the serial number arithmetic of [RFC1982].
+/// It is taken to be the time closest to `now` with the same lower 32 bits, which
+/// keeps cookies working when the Unix time wraps around in 2106. That time is
+/// returned in Unix seconds.
+///
+/// [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018#section-4.3
+/// [RFC1982]: https://datatracker.ietf.org/doc/html/rfc1982
+fn check_timestamp(timing: &Timing, now: i64, timestamp: u32) -> Result<i64, Error> {
+    let distance = i64::from(timestamp.wrapping_sub(now as u32) as i32);
+    if distance < -secs(timing.lifetime).min(SERIAL_RANGE) {
+        return Err(Error::Expired);
+    } else if distance > secs(timing.skew).min(SERIAL_RANGE) {
+        return Err(Error::TimeTravellor);
+    }
+    Ok(now + distance)
+}
+
+/// Converts a duration to whole seconds, saturating at `i64::MAX`
+fn secs(duration: Duration) -> i64 {
+    i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+}
+
 /// A 64-bit Client Cookie
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 #[must_use]
@@ -221,44 +429,76 @@ pub struct Client {
 
 impl Client {
     /// Creates a new client cookie
+    ///
+    /// The FNV algorithm hashes the secret after the addresses, the others are
+    /// keyed with it.
     pub fn new(
         version: Version,
         algorithm: Algorithm,
         client_ip: IpAddr,
         server_ip: IpAddr,
-        client_secret: &[u8],
+        client_secret: &impl AsRef<Secret>,
     ) -> Self {
         match version {
-            Version::One => match algorithm {
-                Algorithm::SipHash24 => {
-                    let mut hasher = SipHasher24::new();
-                    match client_ip {
-                        IpAddr::V4(ip) => hasher.write(&ip.octets()),
-                        IpAddr::V6(ip) => hasher.write(&ip.octets()),
-                    }
-                    match server_ip {
-                        IpAddr::V4(ip) => hasher.write(&ip.octets()),
-                        IpAddr::V6(ip) => hasher.write(&ip.octets()),
-                    }
-                    hasher.write(client_secret);
-                    Self {
-                        hash: hasher.finish(),
-                    }
+            Version::One => {
+                let mut message = Message::new();
+                message.write_ip(client_ip);
+                message.write_ip(server_ip);
+                Self {
+                    hash: u64::from_le_bytes(
+                        algorithm.mac(client_secret.as_ref(), message.as_bytes()),
+                    ),
                 }
-            },
+            }
+        }
+    }
+
+    /// Creates a new client cookie with a MAC of choice
+    pub fn new_with_mac(
+        version: Version,
+        client_ip: IpAddr,
+        server_ip: IpAddr,
+        client_secret: &impl CookieMac,
+    ) -> Self {
+        match version {
+            Version::One => {
+                let mut message = Message::new();
+                message.write_ip(client_ip);
+                message.write_ip(server_ip);
+                Self {
+                    hash: u64::from_le_bytes(client_secret.mac(message.as_bytes())),
+                }
+            }
+        }
+    }
+
+    /// Creates a client cookie from bytes
+    pub const fn decode(client_cookie: [u8; CLIENT_COOKIE_LEN]) -> Self {
+        Self {
+            hash: u64::from_le_bytes(client_cookie),
         }
     }
 
     /// Converts a client cookie to bytes
     #[must_use]
     pub const fn encode(self) -> [u8; CLIENT_COOKIE_LEN] {
-        self.hash.to_be_bytes()
+        self.hash.to_le_bytes()
+    }
+
+    /// Checks that `client_cookie` holds the bytes of this client cookie
+    ///
+    /// The bytes are compared in constant time so how long the check takes does
+    /// not depend on how many of them match.
+    #[must_use]
+    pub fn verify(&self, client_cookie: &[u8; CLIENT_COOKIE_LEN]) -> bool {
+        self.encode().ct_eq(client_cookie).into()
     }
 }
 
+/// Compares in constant time, see [`Client::verify`]
 impl PartialEq<[u8; CLIENT_COOKIE_LEN]> for Client {
     fn eq(&self, other: &[u8; CLIENT_COOKIE_LEN]) -> bool {
-        self.hash == u64::from_be_bytes(*other)
+        self.verify(other)
     }
 }
 
@@ -267,20 +507,24 @@ impl PartialEq<[u8; CLIENT_COOKIE_LEN]> for Client {
 #[must_use]
 pub enum Error {
     IncorrectLength(usize),
-    TimestampRange(time::error::ComponentRange),
+    SecretLength(usize),
     InvalidHash,
     Expired,
     TimeTravellor,
     UnknownVersion(u8),
     UnknownAlgorithm(u8),
     UnsupportedAlgorithm(&'static str),
+    UnknownOption(u16),
+    BufferTooSmall(usize),
+    KeyFileLength(usize),
+    KeyFileDigit(char),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::IncorrectLength(len) => write!(f, "cookie has an incorrect length ({})", len),
-            Error::TimestampRange(error) => write!(f, "{}", error),
+            Error::SecretLength(len) => write!(f, "secret has an incorrect length ({})", len),
             Error::InvalidHash => write!(f, "cookie has an invalid hash"),
             Error::Expired => write!(f, "cookie has expired"),
             Error::TimeTravellor => write!(f, "cookie has a timestamp from the future"),
@@ -293,6 +537,20 @@ impl fmt::Display for Error {
             Error::UnsupportedAlgorithm(algorithm) => {
                 write!(f, "cookie has an unsupported algorithm ({})", algorithm)
             }
+            Error::UnknownOption(code) => write!(f, "option is not a cookie ({})", code),
+            Error::BufferTooSmall(len) => {
+                write!(f, "buffer is too small ({} bytes needed)", len)
+            }
+            Error::KeyFileLength(len) => {
+                write!(
+                    f,
+                    "key file has an incorrect number of hex digits ({})",
+                    len
+                )
+            }
+            Error::KeyFileDigit(digit) => {
+                write!(f, "key file has an invalid hex digit ({:?})", digit)
+            }
         }
     }
 }
@@ -0,0 +1,90 @@
+use crate::fnv::Fnv64;
+#[cfg(feature = "hmac-sha256")]
+use crate::hmac_sha256::HmacSha256;
+use crate::{Algorithm, IpAddr, Secret};
+use core::hash::Hasher;
+use siphasher::sip::SipHasher24;
+
+/// The longest message hashed by this crate, a Client Cookie with two IPv6 addresses
+const MAX_MESSAGE_LEN: usize = 32;
+
+/// A keyed hash function with a 64-bit output used to calculate cookies
+///
+/// [`Secret`] implements it with SipHash-2-4 as prescribed by RFC9018, which is
+/// what the cookies of this crate use unless told otherwise. Implement it to
+/// keep secrets in an HSM or to experiment with other MACs.
+pub trait CookieMac {
+    /// Calculates the MAC of a message
+    ///
+    /// The output is used as the Hash of a cookie as is, in the order it is returned.
+    fn mac(&self, message: &[u8]) -> [u8; 8];
+}
+
+impl<M: CookieMac + ?Sized> CookieMac for &M {
+    fn mac(&self, message: &[u8]) -> [u8; 8] {
+        (**self).mac(message)
+    }
+}
+
+impl CookieMac for Secret {
+    fn mac(&self, message: &[u8]) -> [u8; 8] {
+        let mut hasher = SipHasher24::new_with_key(self.as_bytes());
+        hasher.write(message);
+        hasher.finish().to_le_bytes()
+    }
+}
+
+impl Algorithm {
+    /// Calculates the MAC of a message with the algorithm
+    ///
+    /// FNV is not keyed so the secret is hashed after the message.
+    pub(crate) fn mac(self, secret: &Secret, message: &[u8]) -> [u8; 8] {
+        let hash = match self {
+            Algorithm::SipHash24 => return secret.mac(message),
+            Algorithm::Fnv => {
+                let mut hasher = Fnv64::new();
+                hasher.write(message);
+                hasher.write(secret.as_bytes());
+                hasher.finish()
+            }
+            #[cfg(feature = "hmac-sha256")]
+            Algorithm::HmacSha256 => {
+                let mut hasher = HmacSha256::new_with_key(secret.as_bytes());
+                hasher.write(message);
+                hasher.finish()
+            }
+        };
+        hash.to_le_bytes()
+    }
+}
+
+/// The concatenated fields a MAC is calculated over
+pub(crate) struct Message {
+    bytes: [u8; MAX_MESSAGE_LEN],
+    len: usize,
+}
+
+impl Message {
+    pub(crate) const fn new() -> Self {
+        Self {
+            bytes: [0; MAX_MESSAGE_LEN],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    pub(crate) fn write_ip(&mut self, ip: IpAddr) {
+        match ip {
+            IpAddr::V4(ip) => self.write(&ip.octets()),
+            IpAddr::V6(ip) => self.write(&ip.octets()),
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{Client, Clock, CookieMac, Error, IpAddr, Server};
+use crate::{CLIENT_COOKIE_LEN, SERVER_COOKIE_LEN};
+
+const OPTION_HEADER_LEN: usize = 4;
+const MIN_SERVER_COOKIE_LEN: usize = 8;
+const MAX_SERVER_COOKIE_LEN: usize = 32;
+const MAX_DATA_LEN: usize = CLIENT_COOKIE_LEN + MAX_SERVER_COOKIE_LEN;
+
+/// The EDNS0 COOKIE option as defined by [RFC7873]
+///
+/// The option carries an 8 byte Client Cookie optionally followed by a Server
+/// Cookie of 8 to 32 bytes. Server Cookies of other implementations are kept
+/// as received, only 16 byte cookies can be decoded into a [`Server`].
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#section-4
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct CookieOption {
+    data: [u8; MAX_DATA_LEN],
+    len: usize,
+}
+
+impl CookieOption {
+    /// The EDNS0 option code assigned to COOKIE
+    pub const CODE: u16 = 10;
+
+    /// Creates a new option from a Client Cookie and a possibly empty Server Cookie
+    pub fn new(
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        server_cookie: &[u8],
+    ) -> Result<Self, Error> {
+        let len = CLIENT_COOKIE_LEN + server_cookie.len();
+        if !server_cookie.is_empty()
+            && !(MIN_SERVER_COOKIE_LEN..=MAX_SERVER_COOKIE_LEN).contains(&server_cookie.len())
+        {
+            return Err(Error::IncorrectLength(len));
+        }
+        let mut data = [0; MAX_DATA_LEN];
+        data[..CLIENT_COOKIE_LEN].copy_from_slice(&client_cookie);
+        data[CLIENT_COOKIE_LEN..len].copy_from_slice(server_cookie);
+        Ok(Self { data, len })
+    }
+
+    /// Creates a new option from the cookies of this crate
+    pub fn from_cookies(client: Client, server: Option<Server>) -> Self {
+        let mut data = [0; MAX_DATA_LEN];
+        data[..CLIENT_COOKIE_LEN].copy_from_slice(&client.encode());
+        let len = match server {
+            Some(server) => {
+                data[CLIENT_COOKIE_LEN..CLIENT_COOKIE_LEN + SERVER_COOKIE_LEN]
+                    .copy_from_slice(&server.encode());
+                CLIENT_COOKIE_LEN + SERVER_COOKIE_LEN
+            }
+            None => CLIENT_COOKIE_LEN,
+        };
+        Self { data, len }
+    }
+
+    /// Parses a complete option, including its code and length
+    pub fn decode(option: &[u8]) -> Result<Self, Error> {
+        if option.len() < OPTION_HEADER_LEN {
+            return Err(Error::IncorrectLength(option.len()));
+        }
+        let code = u16::from_be_bytes([option[0], option[1]]);
+        if code != Self::CODE {
+            return Err(Error::UnknownOption(code));
+        }
+        let len = u16::from_be_bytes([option[2], option[3]]) as usize;
+        let data = &option[OPTION_HEADER_LEN..];
+        if data.len() != len {
+            return Err(Error::IncorrectLength(data.len()));
+        }
+        Self::decode_data(data)
+    }
+
+    /// Parses the data of an option whose code and length were already read
+    pub fn decode_data(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < CLIENT_COOKIE_LEN {
+            return Err(Error::IncorrectLength(data.len()));
+        }
+        let mut client_cookie = [0; CLIENT_COOKIE_LEN];
+        client_cookie.copy_from_slice(&data[..CLIENT_COOKIE_LEN]);
+        Self::new(client_cookie, &data[CLIENT_COOKIE_LEN..])
+    }
+
+    /// Returns the Client Cookie as received
+    #[must_use]
+    pub fn client_cookie(&self) -> [u8; CLIENT_COOKIE_LEN] {
+        let mut client_cookie = [0; CLIENT_COOKIE_LEN];
+        client_cookie.copy_from_slice(&self.data[..CLIENT_COOKIE_LEN]);
+        client_cookie
+    }
+
+    /// Returns the Client Cookie
+    pub fn client(&self) -> Client {
+        Client::decode(self.client_cookie())
+    }
+
+    /// Returns the Server Cookie as received, if any
+    #[must_use]
+    pub fn server_cookie(&self) -> Option<&[u8]> {
+        if self.len == CLIENT_COOKIE_LEN {
+            return None;
+        }
+        Some(&self.data[CLIENT_COOKIE_LEN..self.len])
+    }
+
+    /// Validates the Server Cookie, if any
+    ///
+    /// See [`Server::decode`] for the meaning of the arguments.
+    pub fn server(
+        &self,
+        now: impl Clock,
+        client_ip: IpAddr,
+        server_secrets: &[impl CookieMac],
+    ) -> Option<Result<(Server, usize), Error>> {
+        self.server_cookie().map(|server_cookie| {
+            Server::decode(
+                now,
+                self.client_cookie(),
+                client_ip,
+                server_cookie,
+                server_secrets,
+            )
+        })
+    }
+
+    /// Returns the option data, that is the Client Cookie followed by the Server Cookie
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Returns the length of the complete option, including its code and length
+    #[must_use]
+    pub const fn encoded_len(&self) -> usize {
+        OPTION_HEADER_LEN + self.len
+    }
+
+    /// Writes the complete option, including its code and length, and returns its length
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let len = self.encoded_len();
+        if buf.len() < len {
+            return Err(Error::BufferTooSmall(len));
+        }
+        buf[..2].copy_from_slice(&Self::CODE.to_be_bytes());
+        buf[2..OPTION_HEADER_LEN].copy_from_slice(&(self.len as u16).to_be_bytes());
+        buf[OPTION_HEADER_LEN..len].copy_from_slice(self.data());
+        Ok(len)
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{Clock, CookieMac, CookieOption, Error, IpAddr, Server, Timing, Version};
+use crate::{SecretRing, SecretRole, CLIENT_COOKIE_LEN};
+use core::slice;
+
+/// The extended RCODE of responses rejecting a request for lack of a valid Server Cookie
+pub const BADCOOKIE: u16 = 23;
+
+/// What a server should do with a request, as decided by [`ServerPolicy::process`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub enum ServerAction {
+    /// Process the request normally, attaching the option to the response if there is one
+    Proceed(Option<CookieOption>),
+    /// Process the request, attaching the option which carries a fresh Server Cookie
+    Fresh(CookieOption),
+    /// Respond with BADCOOKIE, attaching the option which carries a fresh Server Cookie
+    BadCookie(CookieOption),
+    /// Respond with FORMERR as the COOKIE option is malformed
+    FormErr,
+}
+
+impl ServerAction {
+    /// Returns the (extended) RCODE of the response
+    #[must_use]
+    pub const fn rcode(&self) -> u16 {
+        match self {
+            ServerAction::Proceed(_) | ServerAction::Fresh(_) => 0,
+            ServerAction::BadCookie(_) => BADCOOKIE,
+            ServerAction::FormErr => 1,
+        }
+    }
+
+    /// Returns the option to attach to the response, if any
+    #[must_use]
+    pub const fn option(&self) -> Option<&CookieOption> {
+        match self {
+            ServerAction::Proceed(option) => option.as_ref(),
+            ServerAction::Fresh(option) | ServerAction::BadCookie(option) => Some(option),
+            ServerAction::FormErr => None,
+        }
+    }
+}
+
+/// Processes requests on a server as prescribed by [RFC7873]
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#section-5.2
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[must_use]
+pub struct ServerPolicy {
+    enforce: bool,
+    timing: Timing,
+}
+
+impl ServerPolicy {
+    /// Creates a policy which processes requests without a valid Server Cookie
+    pub const fn new() -> Self {
+        Self {
+            enforce: false,
+            timing: Timing::new(),
+        }
+    }
+
+    /// Answers requests carrying a COOKIE option but no valid Server Cookie with BADCOOKIE
+    pub const fn enforce(mut self, enforce: bool) -> Self {
+        self.enforce = enforce;
+        self
+    }
+
+    /// Sets the timing used to validate and regenerate cookies
+    pub const fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Decides what to do with a request
+    ///
+    /// `option` is the data of the COOKIE option of the request, if any.
+    /// `server_secret` creates new cookies and, along with `other_secrets`,
+    /// validates received cookies. Valid cookies created with one of
+    /// `other_secrets` are replaced by a fresh one.
+    pub fn process<M: CookieMac>(
+        &self,
+        option: Option<&[u8]>,
+        client_ip: IpAddr,
+        now: impl Clock,
+        server_secret: &M,
+        other_secrets: &[M],
+    ) -> ServerAction {
+        let now = now.now();
+        self.respond(
+            option,
+            client_ip,
+            now,
+            server_secret,
+            |client_cookie, server_cookie| {
+                let decode = |server_secrets: &[M]| {
+                    Server::decode_with(
+                        &self.timing,
+                        now,
+                        client_cookie,
+                        client_ip,
+                        server_cookie,
+                        server_secrets,
+                    )
+                };
+                match decode(slice::from_ref(server_secret)) {
+                    Err(Error::InvalidHash) => {
+                        decode(other_secrets).map(|(server, _)| (server, false))
+                    }
+                    result => result.map(|(server, _)| (server, true)),
+                }
+            },
+        )
+    }
+
+    /// Decides what to do with a request, validating cookies with the secrets of a ring
+    ///
+    /// New cookies are created with the active secret of `ring`. Valid cookies
+    /// created with a retiring secret are replaced by a fresh one, as RFC9018
+    /// prescribes for secret rollover. The timing of the policy is used, see
+    /// [`ServerPolicy::process`] for the other arguments.
+    pub fn process_ring<S: CookieMac>(
+        &self,
+        option: Option<&[u8]>,
+        client_ip: IpAddr,
+        now: impl Clock,
+        ring: &SecretRing<S>,
+    ) -> ServerAction {
+        let now = now.now();
+        self.respond(
+            option,
+            client_ip,
+            now,
+            ring.secret(now),
+            |client_cookie, server_cookie| {
+                for (secret, role) in ring.secrets(now) {
+                    match Server::decode_with(
+                        &self.timing,
+                        now,
+                        client_cookie,
+                        client_ip,
+                        server_cookie,
+                        slice::from_ref(secret),
+                    ) {
+                        Ok((server, _)) => return Ok((server, role != SecretRole::Retiring)),
+                        Err(Error::InvalidHash) => continue,
+                        Err(error) => return Err(error),
+                    }
+                }
+                Err(Error::InvalidHash)
+            },
+        )
+    }
+
+    /// Builds the action for a request once `validate` has checked its Server Cookie
+    ///
+    /// `validate` returns the decoded cookie along with whether it may be kept,
+    /// cookies which may not are replaced by a fresh one.
+    fn respond(
+        &self,
+        option: Option<&[u8]>,
+        client_ip: IpAddr,
+        now: i64,
+        server_secret: &impl CookieMac,
+        validate: impl FnOnce([u8; CLIENT_COOKIE_LEN], &[u8]) -> Result<(Server, bool), Error>,
+    ) -> ServerAction {
+        let option = match option.map(CookieOption::decode_data) {
+            None => return ServerAction::Proceed(None),
+            Some(Err(_)) => return ServerAction::FormErr,
+            Some(Ok(option)) => option,
+        };
+        let fresh = || {
+            let server = Server::new(
+                Version::One,
+                [0; 3],
+                now,
+                option.client_cookie(),
+                client_ip,
+                server_secret,
+            );
+            CookieOption::from_cookies(option.client(), Some(server))
+        };
+        let server = option
+            .server_cookie()
+            .map(|server_cookie| validate(option.client_cookie(), server_cookie));
+        match server {
+            Some(Ok((server, true))) => {
+                let server = server.regenerate_with(&self.timing, now, server_secret);
+                ServerAction::Proceed(Some(CookieOption::from_cookies(
+                    option.client(),
+                    Some(server),
+                )))
+            }
+            Some(Ok((_, false))) => ServerAction::Proceed(Some(fresh())),
+            _ if self.enforce => ServerAction::BadCookie(fresh()),
+            _ => ServerAction::Fresh(fresh()),
+        }
+    }
+}
+
+/// What a client should do with a response, as decided by [`ClientPolicy::process`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub enum ClientAction {
+    /// Accept the response, remembering the option which carries the Server Cookie if there is one
+    Accept(Option<CookieOption>),
+    /// Discard the response as malformed or spoofed and keep waiting for the genuine one
+    Discard,
+    /// Resend the query with the option, which carries the Server Cookie the server just handed out
+    Retry(CookieOption),
+    /// Stop resending the query as the server keeps answering BADCOOKIE, the
+    /// option carries the Server Cookie it handed out last
+    GiveUp(CookieOption),
+}
+
+/// Processes responses on a client as prescribed by [RFC7873]
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#section-5.3
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct ClientPolicy {
+    retries: u8,
+}
+
+impl ClientPolicy {
+    /// Creates a policy which resends a query once when it is answered with BADCOOKIE
+    pub const fn new() -> Self {
+        Self { retries: 1 }
+    }
+
+    /// Sets how many times a query is resent when it is answered with BADCOOKIE
+    pub const fn retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Decides what to do with a response
+    ///
+    /// `query` is the COOKIE option the query was sent with, `rcode` the
+    /// (extended) RCODE of the response, `option` the data of its COOKIE option
+    /// if any and `retries` how many times the query was already resent.
+    ///
+    /// A response is discarded when its Client Cookie differs from the one that
+    /// was sent, or when it lacks a COOKIE option while the query carried a
+    /// Server Cookie, as the server is then known to support cookies.
+    pub fn process(
+        &self,
+        query: &CookieOption,
+        rcode: u16,
+        option: Option<&[u8]>,
+        retries: u8,
+    ) -> ClientAction {
+        let option = match option.map(CookieOption::decode_data) {
+            None if query.server_cookie().is_some() => return ClientAction::Discard,
+            None if rcode == BADCOOKIE => return ClientAction::Discard,
+            None => return ClientAction::Accept(None),
+            Some(Err(_)) => return ClientAction::Discard,
+            Some(Ok(option)) => option,
+        };
+        if !query.client().verify(&option.client_cookie()) {
+            return ClientAction::Discard;
+        }
+        let has_server_cookie = option.server_cookie().is_some();
+        match rcode {
+            BADCOOKIE if !has_server_cookie => ClientAction::Discard,
+            BADCOOKIE if retries < self.retries => ClientAction::Retry(option),
+            BADCOOKIE => ClientAction::GiveUp(option),
+            _ if has_server_cookie => ClientAction::Accept(Some(option)),
+            _ => ClientAction::Accept(None),
+        }
+    }
+}
+
+impl Default for ClientPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{ClientAction, ClientCache, ClientPolicy, CookieOption, IpAddr, BADCOOKIE};
+
+/// How a client should go on after a BADCOOKIE response, as decided by [`BadCookieRetry::process`]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub enum Retry {
+    /// Resend the query over UDP with the option
+    Udp(CookieOption),
+    /// Resend the query over TCP with the option
+    Tcp(CookieOption),
+    /// Give up on the query as the server keeps answering BADCOOKIE, even over TCP
+    Fail,
+    /// Discard the response as malformed or spoofed and keep waiting for the genuine one
+    Discard,
+}
+
+/// Tracks the BADCOOKIE responses to a query as prescribed by [RFC7873]
+///
+/// A query answered with BADCOOKIE is resent over UDP with the Server Cookie
+/// carried by the response. If the server keeps answering BADCOOKIE, the query
+/// is resent over TCP before giving up. Create one per query.
+///
+/// [RFC7873]: https://datatracker.ietf.org/doc/html/rfc7873#section-5.3
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct BadCookieRetry {
+    policy: ClientPolicy,
+    udp_resent: u8,
+    tcp: bool,
+}
+
+impl BadCookieRetry {
+    /// Creates a tracker which resends the query over UDP once before switching to TCP
+    pub const fn new() -> Self {
+        Self {
+            policy: ClientPolicy::new(),
+            udp_resent: 0,
+            tcp: false,
+        }
+    }
+
+    /// Sets how many times the query is resent over UDP before switching to TCP
+    pub const fn udp_retries(mut self, udp_retries: u8) -> Self {
+        self.policy = self.policy.retries(udp_retries);
+        self
+    }
+
+    /// Decides how to go on after a BADCOOKIE response from a server
+    ///
+    /// `query` is the COOKIE option the query was sent with and `option` the
+    /// data of the COOKIE option of the response, if any. The response is
+    /// validated by [`ClientPolicy::process`] and the Server Cookie carried by
+    /// a genuine response is remembered in `cache`.
+    pub fn process<const N: usize>(
+        &mut self,
+        cache: &mut ClientCache<N>,
+        server_ip: IpAddr,
+        query: &CookieOption,
+        option: Option<&[u8]>,
+    ) -> Retry {
+        match self
+            .policy
+            .process(query, BADCOOKIE, option, self.udp_resent)
+        {
+            ClientAction::Retry(option) => {
+                cache.update(server_ip, option);
+                self.udp_resent += 1;
+                Retry::Udp(option)
+            }
+            ClientAction::GiveUp(option) => {
+                cache.update(server_ip, option);
+                if self.tcp {
+                    return Retry::Fail;
+                }
+                self.tcp = true;
+                Retry::Tcp(option)
+            }
+            // BADCOOKIE responses are never accepted
+            ClientAction::Accept(_) | ClientAction::Discard => Retry::Discard,
+        }
+    }
+}
+
+impl Default for BadCookieRetry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
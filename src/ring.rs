@@ -0,0 +1,197 @@
+use crate::CLIENT_COOKIE_LEN;
+use crate::{secs, Clock, CookieMac, Error, IpAddr, Secret, Server, Timing, Version};
+use core::{mem, slice};
+
+/// The part a secret of a [`SecretRing`] plays at a given time
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[must_use]
+pub enum SecretRole {
+    /// The secret creates and validates cookies
+    Active,
+    /// The secret validates cookies until it becomes active
+    Pending,
+    /// The secret validates cookies until it is retired, cookies it validates should be renewed
+    Retiring,
+}
+
+/// Rolls server secrets over as prescribed by [RFC9018]
+///
+/// A new secret is first introduced for validation only, so that every server
+/// of an anycast set accepts it before any of them creates cookies with it. At
+/// its activation time it replaces the current secret, which keeps validating
+/// cookies for their lifetime before being retired.
+///
+/// Only one secret is retiring at a time: a secret whose activation time comes
+/// while the previous secret is still retiring becomes active once that secret
+/// is retired, so that no cookie is rejected before the end of its lifetime.
+///
+/// The secrets are [`Secret`]s unless told otherwise, use [`ServerSecret`]s
+/// to have them zeroized once they are dropped from the ring.
+///
+/// [RFC9018]: https://datatracker.ietf.org/doc/html/rfc9018#section-5
+/// [`ServerSecret`]: crate::ServerSecret
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct SecretRing<S = Secret> {
+    previous: Option<(S, i64)>,
+    current: S,
+    next: Option<(S, i64)>,
+    timing: Timing,
+}
+
+impl<S> SecretRing<S> {
+    /// Creates a ring with a single active secret
+    pub const fn new(secret: S) -> Self {
+        Self {
+            previous: None,
+            current: secret,
+            next: None,
+            timing: Timing::new(),
+        }
+    }
+
+    /// Sets the timing of the cookies, which also decides when secrets are retired
+    pub const fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+}
+
+impl<S: CookieMac> SecretRing<S> {
+    /// Introduces a secret which validates cookies right away and creates them from `activation`
+    ///
+    /// `activation` is in Unix seconds, the secret is held back past it while
+    /// another secret is retiring. A secret that was introduced before but is
+    /// not active yet is replaced.
+    pub fn introduce(&mut self, now: impl Clock, secret: S, activation: i64) {
+        self.rotate(now);
+        self.next = Some((secret, activation));
+    }
+
+    /// Activates the introduced secret and drops the retired one once their time has come
+    ///
+    /// The other methods take rotation into account on their own, calling this
+    /// only frees the slots of secrets that no longer play any part.
+    pub fn rotate(&mut self, now: impl Clock) {
+        let now = now.now();
+        if let Some(activation) = self.activation().filter(|activation| *activation <= now) {
+            if let Some((secret, _)) = self.next.take() {
+                let current = mem::replace(&mut self.current, secret);
+                self.previous = Some((current, activation));
+            }
+        }
+        if let Some((_, deactivation)) = &self.previous {
+            if deactivation.saturating_add(secs(self.timing.lifetime)) <= now {
+                self.previous = None;
+            }
+        }
+    }
+
+    /// Returns when the introduced secret becomes active, if there is one
+    ///
+    /// That is its activation time or, if it comes first, the time the
+    /// retiring secret is retired.
+    fn activation(&self) -> Option<i64> {
+        let (_, activation) = self.next.as_ref()?;
+        let lifetime = secs(self.timing.lifetime);
+        Some(match &self.previous {
+            Some((_, deactivation)) => (*activation).max(deactivation.saturating_add(lifetime)),
+            None => *activation,
+        })
+    }
+
+    /// Returns the secret which creates cookies
+    pub fn secret(&self, now: impl Clock) -> &S {
+        match (&self.next, self.activation()) {
+            (Some((secret, _)), Some(activation)) if activation <= now.now() => secret,
+            _ => &self.current,
+        }
+    }
+
+    /// Returns the secrets which validate cookies, the active one first
+    pub fn secrets(&self, now: impl Clock) -> impl Iterator<Item = (&S, SecretRole)> + '_ {
+        let now = now.now();
+        let lifetime = secs(self.timing.lifetime);
+        let retiring = move |deactivation: i64| now < deactivation.saturating_add(lifetime);
+        let previous = self
+            .previous
+            .as_ref()
+            .filter(|(_, deactivation)| retiring(*deactivation))
+            .map(|(secret, _)| (secret, SecretRole::Retiring));
+        let secrets = match (&self.next, self.activation()) {
+            (Some((next, _)), Some(activation)) if activation <= now => [
+                Some((next, SecretRole::Active)),
+                Some((&self.current, SecretRole::Retiring)).filter(|_| retiring(activation)),
+                previous,
+            ],
+            (next, _) => [
+                Some((&self.current, SecretRole::Active)),
+                next.as_ref().map(|(next, _)| (next, SecretRole::Pending)),
+                previous,
+            ],
+        };
+        IntoIterator::into_iter(secrets).flatten()
+    }
+
+    /// Creates a new server cookie with the active secret
+    pub fn create(
+        &self,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+    ) -> Server {
+        let now = now.now();
+        Server::new(
+            Version::One,
+            [0; 3],
+            now,
+            client_cookie,
+            client_ip,
+            self.secret(now),
+        )
+    }
+
+    /// Creates and validates a server cookie from bytes, reporting the role of the secret that validated it
+    ///
+    /// See [`Server::decode`] for the meaning of the arguments.
+    pub fn decode(
+        &self,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_cookie: &[u8],
+    ) -> Result<(Server, SecretRole), Error> {
+        let now = now.now();
+        for (secret, role) in self.secrets(now) {
+            match Server::decode_with(
+                &self.timing,
+                now,
+                client_cookie,
+                client_ip,
+                server_cookie,
+                slice::from_ref(secret),
+            ) {
+                Ok((server, _)) => return Ok((server, role)),
+                Err(Error::InvalidHash) => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Err(Error::InvalidHash)
+    }
+
+    /// Regenerates a server cookie validated by a secret of the ring
+    ///
+    /// Cookies validated by a retiring secret are always replaced by a cookie
+    /// created with the active secret.
+    pub fn regenerate(&self, now: impl Clock, server: Server, role: SecretRole) -> Server {
+        let now = now.now();
+        match role {
+            SecretRole::Retiring => {
+                self.create(now, server.data.client_cookie, server.data.client_ip)
+            }
+            SecretRole::Active | SecretRole::Pending => {
+                server.regenerate_with(&self.timing, now, self.secret(now))
+            }
+        }
+    }
+}
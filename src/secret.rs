@@ -0,0 +1,152 @@
+use crate::{CookieMac, Error, Secret, SECRET_LEN};
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRngCore;
+use zeroize::{Zeroize, Zeroizing};
+
+/// The length of a key file, the secret in lowercase hex digits followed by a newline
+const KEY_FILE_LEN: usize = 2 * SECRET_LEN + 1;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+impl Secret {
+    /// Generates a new secret from a cryptographically secure random number generator
+    #[cfg(feature = "rand_core")]
+    pub fn generate(rng: &mut impl CryptoRngCore) -> Self {
+        let mut secret = [0; SECRET_LEN];
+        rng.fill_bytes(&mut secret);
+        Self(secret)
+    }
+
+    /// Reads a secret from the contents of a key file
+    ///
+    /// A key file holds the secret as 32 hex digits, in either case, optionally
+    /// surrounded by whitespace.
+    pub fn from_key_file(contents: &str) -> Result<Self, Error> {
+        // Partly decoded key material is zeroized on every way out
+        let mut secret = Zeroizing::new([0; SECRET_LEN]);
+        let mut digits = 0;
+        for c in contents.trim().chars() {
+            let digit = c.to_digit(16).ok_or(Error::KeyFileDigit(c))? as u8;
+            if let Some(byte) = secret.get_mut(digits / 2) {
+                *byte |= digit << (4 * (1 - digits % 2));
+            }
+            digits += 1;
+        }
+        if digits != 2 * SECRET_LEN {
+            return Err(Error::KeyFileLength(digits));
+        }
+        Ok(Self(*secret))
+    }
+
+    /// Returns the contents of a key file holding the secret
+    ///
+    /// The secret is written in lowercase hex digits followed by a newline.
+    /// The contents are zeroized when they are dropped.
+    #[must_use]
+    pub fn to_key_file(&self) -> Zeroizing<[u8; KEY_FILE_LEN]> {
+        let mut contents = Zeroizing::new([b'\n'; KEY_FILE_LEN]);
+        for (i, byte) in self.0.iter().enumerate() {
+            contents[2 * i] = HEX_DIGITS[(byte >> 4) as usize];
+            contents[2 * i + 1] = HEX_DIGITS[(byte & 0xf) as usize];
+        }
+        contents
+    }
+}
+
+macro_rules! owned_secret {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        ///
+        /// Unlike [`Secret`], it cannot be copied and its key material is
+        /// zeroized when it is dropped. Its `Debug` output hides the key material.
+        #[derive(Clone)]
+        #[must_use]
+        pub struct $name(Secret);
+
+        impl $name {
+            /// Creates a new secret
+            pub const fn new(secret: [u8; SECRET_LEN]) -> Self {
+                Self(Secret::new(secret))
+            }
+
+            /// Generates a new secret from a cryptographically secure random number generator
+            #[cfg(feature = "rand_core")]
+            pub fn generate(rng: &mut impl CryptoRngCore) -> Self {
+                Self(Secret::generate(rng))
+            }
+
+            /// Reads a secret from the contents of a key file, see [`Secret::from_key_file`]
+            pub fn from_key_file(contents: &str) -> Result<Self, Error> {
+                Secret::from_key_file(contents).map(Self)
+            }
+
+            /// Returns the contents of a key file holding the secret, see [`Secret::to_key_file`]
+            #[must_use]
+            pub fn to_key_file(&self) -> Zeroizing<[u8; KEY_FILE_LEN]> {
+                self.0.to_key_file()
+            }
+
+            /// Returns the key material of the secret
+            #[must_use]
+            pub const fn as_bytes(&self) -> &[u8; SECRET_LEN] {
+                self.0.as_bytes()
+            }
+        }
+
+        impl From<[u8; SECRET_LEN]> for $name {
+            fn from(secret: [u8; SECRET_LEN]) -> Self {
+                Self::new(secret)
+            }
+        }
+
+        impl From<Secret> for $name {
+            fn from(secret: Secret) -> Self {
+                Self(secret)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = Error;
+
+            fn try_from(secret: &[u8]) -> Result<Self, Self::Error> {
+                Secret::try_from(secret).map(Self)
+            }
+        }
+
+        impl AsRef<Secret> for $name {
+            fn as_ref(&self) -> &Secret {
+                &self.0
+            }
+        }
+
+        impl CookieMac for $name {
+            fn mac(&self, message: &[u8]) -> [u8; 8] {
+                self.0.mac(message)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(concat!(stringify!($name), "(..)"))
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                (self.0).0.zeroize();
+            }
+        }
+    };
+}
+
+owned_secret! {
+    /// An owned secret for generating and validating Server Cookies
+    ServerSecret
+}
+
+owned_secret! {
+    /// An owned secret for generating Client Cookies
+    ClientSecret
+}
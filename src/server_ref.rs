@@ -0,0 +1,125 @@
+use crate::mac::Message;
+use crate::{check_timestamp, Clock, CookieMac, Data, Error, IpAddr, Server, Timing, Version};
+use crate::{CLIENT_COOKIE_LEN, SERVER_COOKIE_LEN};
+use core::convert::TryFrom;
+use subtle::ConstantTimeEq;
+
+/// A borrowed view of a 128-bit Server Cookie as received on the wire
+///
+/// The fields are read straight from the received bytes and the cheap checks,
+/// length, version and time window, are done before any hashing so junk cookies
+/// are rejected at little cost.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[must_use]
+pub struct ServerCookieRef<'a> {
+    version: Version,
+    bytes: &'a [u8; SERVER_COOKIE_LEN],
+}
+
+impl<'a> ServerCookieRef<'a> {
+    /// Borrows a server cookie after checking its length and version
+    pub fn new(server_cookie: &'a [u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; SERVER_COOKIE_LEN]>::try_from(server_cookie)
+            .map_err(|_| Error::IncorrectLength(server_cookie.len()))?;
+        let version = Version::try_from(bytes[0])?;
+        Ok(Self { version, bytes })
+    }
+
+    /// Returns the version of the cookie
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the reserved field of the cookie
+    #[must_use]
+    pub const fn reserved(&self) -> [u8; 3] {
+        [self.bytes[1], self.bytes[2], self.bytes[3]]
+    }
+
+    /// Returns the lower 32 bits of the Unix time the cookie was created at
+    #[must_use]
+    pub const fn timestamp(&self) -> u32 {
+        u32::from_be_bytes([self.bytes[4], self.bytes[5], self.bytes[6], self.bytes[7]])
+    }
+
+    /// Returns the Hash of the cookie in the order it is received
+    #[must_use]
+    pub const fn hash(&self) -> [u8; 8] {
+        let bytes = self.bytes;
+        [
+            bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15],
+        ]
+    }
+
+    /// Returns the bytes of the cookie
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8; SERVER_COOKIE_LEN] {
+        self.bytes
+    }
+
+    /// Checks that the cookie is within the validity window of `timing` at `now`
+    ///
+    /// The Unix time the cookie was created at is returned, see [`Server::decode`]
+    /// for how it is recovered from the timestamp.
+    pub fn check_time(&self, timing: &Timing, now: impl Clock) -> Result<i64, Error> {
+        check_timestamp(timing, now.now(), self.timestamp())
+    }
+
+    /// Validates the cookie without decoding it
+    ///
+    /// The index of the secret in `server_secrets` that validated it is returned.
+    pub fn validate(
+        &self,
+        timing: &Timing,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_secrets: &[impl CookieMac],
+    ) -> Result<usize, Error> {
+        self.check_time(timing, now)?;
+        self.verify_hash(client_cookie, client_ip, server_secrets)
+    }
+
+    /// Validates the cookie and decodes it into a [`Server`]
+    pub fn decode(
+        &self,
+        timing: &Timing,
+        now: impl Clock,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_secrets: &[impl CookieMac],
+    ) -> Result<(Server, usize), Error> {
+        let time = self.check_time(timing, now)?;
+        let index = self.verify_hash(client_cookie, client_ip, server_secrets)?;
+        let data = Data {
+            version: self.version,
+            reserved: self.reserved(),
+            time,
+            client_cookie,
+            client_ip,
+        };
+        let hash = u64::from_le_bytes(self.hash());
+        Ok((Server { data, hash }, index))
+    }
+
+    /// Finds the secret the Hash of the cookie was calculated with
+    ///
+    /// The message is built once from the received bytes and the Hash is compared
+    /// in constant time.
+    fn verify_hash(
+        &self,
+        client_cookie: [u8; CLIENT_COOKIE_LEN],
+        client_ip: IpAddr,
+        server_secrets: &[impl CookieMac],
+    ) -> Result<usize, Error> {
+        let mut message = Message::new();
+        message.write(&client_cookie);
+        message.write(&self.bytes[..8]);
+        message.write_ip(client_ip);
+        let hash = &self.bytes[8..];
+        server_secrets
+            .iter()
+            .position(|secret| bool::from(secret.mac(message.as_bytes()).ct_eq(hash)))
+            .ok_or(Error::InvalidHash)
+    }
+}
@@ -0,0 +1,112 @@
+//! Test vectors from [Appendix A] of RFC9018
+//!
+//! Each vector holds everything needed to reconstruct or validate the Server
+//! Cookie it carries.
+//!
+//! Vectors captured from BIND, Knot DNS and PowerDNS are yet to be added. They
+//! will get constants of their own, with the version of the server they were
+//! captured from.
+//!
+//! [Appendix A]: https://datatracker.ietf.org/doc/html/rfc9018#appendix-A
+
+use crate::Secret;
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// A Server Cookie together with the inputs it was computed from
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Vector {
+    /// The source address of the query
+    pub client_ip: IpAddr,
+    /// The Client Cookie sent by the client
+    pub client_cookie: [u8; 8],
+    /// The secret the Server Cookie was hashed with
+    pub server_secret: Secret,
+    /// The time of the Server Cookie in seconds since the Unix epoch
+    pub timestamp: u32,
+    /// The Server Cookie on the wire
+    pub server_cookie: [u8; 16],
+}
+
+const SECRET: Secret = Secret::new([
+    0xe5, 0xe9, 0x73, 0xe5, 0xa6, 0xb2, 0xa4, 0x3f, 0x48, 0xe7, 0xdc, 0x84, 0x9e, 0x37, 0xbf, 0xcf,
+]);
+
+const ROLLED_OVER_SECRET: Secret = Secret::new([
+    0xdd, 0x3b, 0xdf, 0x93, 0x44, 0xb6, 0x78, 0xb1, 0x85, 0xa6, 0xf5, 0xcb, 0x60, 0xfc, 0xa7, 0x15,
+]);
+
+/// A.1. Learning a New Server Cookie
+pub const NEW_SERVER_COOKIE: Vector = Vector {
+    client_ip: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100)),
+    client_cookie: [0x24, 0x64, 0xc4, 0xab, 0xcf, 0x10, 0xc9, 0x57],
+    server_secret: SECRET,
+    timestamp: 1_559_731_985,
+    server_cookie: [
+        0x01, 0x00, 0x00, 0x00, 0x5c, 0xf7, 0x9f, 0x11, 0x1f, 0x81, 0x30, 0xc3, 0xee, 0xe2, 0x94,
+        0x80,
+    ],
+};
+
+/// A.2. The Same Client Learning a Renewed (Fresh) Server Cookie
+pub const RENEWED_SERVER_COOKIE: Vector = Vector {
+    client_ip: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100)),
+    client_cookie: [0x24, 0x64, 0xc4, 0xab, 0xcf, 0x10, 0xc9, 0x57],
+    server_secret: SECRET,
+    timestamp: 1_559_734_385,
+    server_cookie: [
+        0x01, 0x00, 0x00, 0x00, 0x5c, 0xf7, 0xa8, 0x71, 0xd4, 0xa5, 0x64, 0xa1, 0x44, 0x2a, 0xca,
+        0x77,
+    ],
+};
+
+/// A.3. Another Client Learning a Renewed Server Cookie, as sent in the query
+///
+/// The Reserved bytes are set and must be fed into the hash as received.
+pub const RESERVED_SERVER_COOKIE: Vector = Vector {
+    client_ip: IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203)),
+    client_cookie: [0xfc, 0x93, 0xfc, 0x62, 0x80, 0x7d, 0xdb, 0x86],
+    server_secret: SECRET,
+    timestamp: 1_559_727_985,
+    server_cookie: [
+        0x01, 0xab, 0xcd, 0xef, 0x5c, 0xf7, 0x8f, 0x71, 0xa3, 0x14, 0x22, 0x7b, 0x66, 0x79, 0xeb,
+        0xf5,
+    ],
+};
+
+/// A.3. Another Client Learning a Renewed Server Cookie, as sent in the response
+pub const RESERVED_RENEWED_SERVER_COOKIE: Vector = Vector {
+    client_ip: IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203)),
+    client_cookie: [0xfc, 0x93, 0xfc, 0x62, 0x80, 0x7d, 0xdb, 0x86],
+    server_secret: SECRET,
+    timestamp: 1_559_734_700,
+    server_cookie: [
+        0x01, 0x00, 0x00, 0x00, 0x5c, 0xf7, 0xa9, 0xac, 0xf7, 0x3a, 0x78, 0x10, 0xac, 0xa2, 0x38,
+        0x1e,
+    ],
+};
+
+/// A.4. IPv6 Query with Rolled Over Secret
+pub const IPV6_ROLLED_OVER_SECRET: Vector = Vector {
+    client_ip: IpAddr::V6(Ipv6Addr::new(
+        0x2001, 0xdb8, 0x220, 0x1, 0x59de, 0xd0f4, 0x8769, 0x82b8,
+    )),
+    client_cookie: [0x22, 0x68, 0x1a, 0xb9, 0x7d, 0x52, 0xc2, 0x98],
+    server_secret: ROLLED_OVER_SECRET,
+    timestamp: 1_559_741_817,
+    server_cookie: [
+        0x01, 0x00, 0x00, 0x00, 0x5c, 0xf7, 0xc5, 0x79, 0x26, 0x55, 0x6b, 0xd0, 0x93, 0x4c, 0x72,
+        0xf8,
+    ],
+};
+
+/// All the vectors of Appendix A
+pub const ALL: [Vector; 5] = [
+    NEW_SERVER_COOKIE,
+    RENEWED_SERVER_COOKIE,
+    RESERVED_SERVER_COOKIE,
+    RESERVED_RENEWED_SERVER_COOKIE,
+    IPV6_ROLLED_OVER_SECRET,
+];
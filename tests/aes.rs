@@ -0,0 +1,100 @@
+#![cfg(feature = "aes")]
+
+use dns_cookie::aes::Server;
+use dns_cookie::{Error, Secret};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const NOW: i64 = 1_559_731_985;
+const NONCE: u32 = 0x1f2e_3d4c;
+const SERVER_SECRET: Secret = Secret::new([
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+]);
+const OTHER: Secret = Secret::new([1; 16]);
+const CLIENT_COOKIE: [u8; 8] = [0x24, 0x64, 0xc4, 0xab, 0xcf, 0x10, 0xc9, 0x57];
+const CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+const IPV6_CLIENT_COOKIE: [u8; 8] = [0x22, 0x68, 0x1a, 0xb9, 0x7d, 0x52, 0xc2, 0x98];
+const IPV6_CLIENT_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(
+    0x2001, 0xdb8, 0x220, 0x1, 0x59de, 0xd0f4, 0x8769, 0x82b8,
+));
+
+/// Calculated with an independent Python implementation of `compute_cookie` in
+/// BIND's `lib/ns/client.c`, not captured from a BIND server
+const SERVER_COOKIE: [u8; 16] = [
+    0x1f, 0x2e, 0x3d, 0x4c, 0x5c, 0xf7, 0x9f, 0x11, 0xfb, 0x41, 0xd0, 0x70, 0x47, 0x6b, 0x6b, 0xa6,
+];
+const IPV6_SERVER_COOKIE: [u8; 16] = [
+    0x1f, 0x2e, 0x3d, 0x4c, 0x5c, 0xf7, 0x9f, 0x11, 0x13, 0x21, 0x82, 0x3b, 0xa9, 0xbc, 0x9f, 0xb1,
+];
+
+#[test]
+fn known_answers() {
+    let server = Server::new(NONCE, NOW, CLIENT_COOKIE, CLIENT_IP, &SERVER_SECRET);
+    assert_eq!(server.encode(), SERVER_COOKIE);
+    assert_eq!(server.nonce(), NONCE);
+    let server = Server::new(
+        NONCE,
+        NOW,
+        IPV6_CLIENT_COOKIE,
+        IPV6_CLIENT_IP,
+        &SERVER_SECRET,
+    );
+    assert_eq!(server.encode(), IPV6_SERVER_COOKIE);
+}
+
+#[test]
+fn decode() {
+    let decode = |now: i64, server_cookie: &[u8], secrets: &[Secret]| {
+        Server::decode(now, CLIENT_COOKIE, CLIENT_IP, server_cookie, secrets)
+    };
+    let server = Server::new(NONCE, NOW, CLIENT_COOKIE, CLIENT_IP, &SERVER_SECRET);
+    assert_eq!(
+        decode(NOW + 60, &SERVER_COOKIE, &[OTHER, SERVER_SECRET]),
+        Ok((server, 1))
+    );
+    assert_eq!(
+        decode(NOW, &SERVER_COOKIE, &[OTHER]),
+        Err(Error::InvalidHash)
+    );
+    assert_eq!(
+        decode(NOW + 3601, &SERVER_COOKIE, &[SERVER_SECRET]),
+        Err(Error::Expired)
+    );
+    assert_eq!(
+        decode(NOW - 301, &SERVER_COOKIE, &[SERVER_SECRET]),
+        Err(Error::TimeTravellor)
+    );
+    assert_eq!(
+        decode(NOW, &SERVER_COOKIE[..15], &[SERVER_SECRET]),
+        Err(Error::IncorrectLength(15))
+    );
+
+    let mut nonce = SERVER_COOKIE;
+    nonce[0] ^= 1;
+    assert_eq!(
+        decode(NOW, &nonce, &[SERVER_SECRET]),
+        Err(Error::InvalidHash)
+    );
+    let other_ip = Server::decode(
+        NOW,
+        CLIENT_COOKIE,
+        IPV6_CLIENT_IP,
+        &SERVER_COOKIE,
+        &[SERVER_SECRET],
+    );
+    assert_eq!(other_ip, Err(Error::InvalidHash));
+}
+
+#[test]
+fn regenerate() {
+    let server = Server::new(NONCE, NOW, CLIENT_COOKIE, CLIENT_IP, &SERVER_SECRET);
+    assert_eq!(server.regenerate(NOW + 1799, &SERVER_SECRET), server);
+    let renewed = server.regenerate(NOW + 1800, &SERVER_SECRET);
+    assert_eq!(renewed.nonce(), NONCE);
+    assert_eq!(
+        renewed,
+        Server::new(NONCE, NOW + 1800, CLIENT_COOKIE, CLIENT_IP, &SERVER_SECRET)
+    );
+}
@@ -0,0 +1,78 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Client, ClientCache, CookieOption};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const FIRST: Vector = test_vectors::NEW_SERVER_COOKIE;
+const SECOND: Vector = test_vectors::RESERVED_SERVER_COOKIE;
+const THIRD: Vector = test_vectors::IPV6_ROLLED_OVER_SECRET;
+const FIRST_SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1));
+const SECOND_SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 2));
+const THIRD_SERVER_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x53));
+
+fn option(vector: &Vector) -> CookieOption {
+    CookieOption::new(vector.client_cookie, &vector.server_cookie).unwrap()
+}
+
+#[test]
+fn echoes_learned_server_cookie() {
+    let mut cache = ClientCache::<4>::new();
+    let client = Client::decode(FIRST.client_cookie);
+    assert_eq!(
+        cache.option(FIRST_SERVER_IP, client),
+        CookieOption::from_cookies(client, None)
+    );
+
+    cache.update(FIRST_SERVER_IP, option(&FIRST));
+    assert_eq!(cache.option(FIRST_SERVER_IP, client), option(&FIRST));
+    assert_eq!(cache.len(), 1);
+
+    cache.update(
+        FIRST_SERVER_IP,
+        option(&test_vectors::RENEWED_SERVER_COOKIE),
+    );
+    assert_eq!(
+        cache.option(FIRST_SERVER_IP, client),
+        option(&test_vectors::RENEWED_SERVER_COOKIE)
+    );
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn other_client_cookie() {
+    let mut cache = ClientCache::<4>::new();
+    cache.update(FIRST_SERVER_IP, option(&FIRST));
+    let client = Client::decode(SECOND.client_cookie);
+    assert_eq!(
+        cache.option(FIRST_SERVER_IP, client),
+        CookieOption::from_cookies(client, None)
+    );
+}
+
+#[test]
+fn ignores_client_cookie_only() {
+    let mut cache = ClientCache::<4>::new();
+    let client = Client::decode(FIRST.client_cookie);
+    cache.update(FIRST_SERVER_IP, CookieOption::from_cookies(client, None));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn evicts_least_recently_updated() {
+    let mut cache = ClientCache::<2>::new();
+    cache.update(FIRST_SERVER_IP, option(&FIRST));
+    cache.update(SECOND_SERVER_IP, option(&SECOND));
+    cache.update(FIRST_SERVER_IP, option(&FIRST));
+    cache.update(THIRD_SERVER_IP, option(&THIRD));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(FIRST_SERVER_IP), Some(&option(&FIRST)));
+    assert_eq!(cache.get(SECOND_SERVER_IP), None);
+    assert_eq!(cache.get(THIRD_SERVER_IP), Some(&option(&THIRD)));
+
+    assert_eq!(cache.remove(FIRST_SERVER_IP), Some(option(&FIRST)));
+    assert_eq!(cache.remove(FIRST_SERVER_IP), None);
+    cache.clear();
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,82 @@
+use dns_cookie::{Algorithm, Client, Error, Secret, Version};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr};
+use siphasher::sip::SipHasher24;
+use std::convert::TryFrom;
+use std::hash::Hasher;
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr};
+
+const CLIENT_OCTETS: [u8; 4] = [198, 51, 100, 100];
+const SERVER_OCTETS: [u8; 4] = [203, 0, 113, 203];
+const CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203));
+const CLIENT_SECRET: Secret = Secret::new([1; 16]);
+
+fn client(algorithm: Algorithm) -> Client {
+    Client::new(
+        Version::One,
+        algorithm,
+        CLIENT_IP,
+        SERVER_IP,
+        &CLIENT_SECRET,
+    )
+}
+
+#[test]
+fn siphash24() {
+    let mut hasher = SipHasher24::new_with_key(CLIENT_SECRET.as_bytes());
+    hasher.write(&CLIENT_OCTETS);
+    hasher.write(&SERVER_OCTETS);
+    let client = client(Algorithm::SipHash24);
+    assert_eq!(client.encode(), hasher.finish().to_le_bytes());
+    assert_eq!(client, client.encode());
+    assert_eq!(Client::decode(client.encode()), client);
+}
+
+/// FNV-1a-64 of the client address, the server address and the secret is
+/// 0x2d8fa23a3166e7ed, as calculated by a reference implementation which hashes
+/// "foobar" to 0x85944171f73967e8
+#[test]
+fn fnv() {
+    let client = client(Algorithm::Fnv);
+    assert_eq!(client.encode(), 0x2d8f_a23a_3166_e7ed_u64.to_le_bytes());
+    assert_eq!(
+        client.encode(),
+        [0xed, 0xe7, 0x66, 0x31, 0x3a, 0xa2, 0x8f, 0x2d]
+    );
+}
+
+#[cfg(feature = "hmac-sha256")]
+#[test]
+fn hmac_sha256() {
+    use hmac::{Hmac, Mac};
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(CLIENT_SECRET.as_bytes()).unwrap();
+    mac.update(&CLIENT_OCTETS);
+    mac.update(&SERVER_OCTETS);
+    let mac = mac.finalize().into_bytes();
+    assert_eq!(client(Algorithm::HmacSha256).encode(), &mac[..8]);
+}
+
+#[test]
+fn algorithms() {
+    assert_eq!(Algorithm::try_from(1), Ok(Algorithm::Fnv));
+    assert_eq!(Algorithm::try_from(4), Ok(Algorithm::SipHash24));
+    #[cfg(feature = "hmac-sha256")]
+    assert_eq!(Algorithm::try_from(2), Ok(Algorithm::HmacSha256));
+    assert_eq!(
+        Algorithm::try_from(3),
+        Err(Error::UnsupportedAlgorithm("AES"))
+    );
+    assert_ne!(client(Algorithm::Fnv), client(Algorithm::SipHash24));
+}
+
+#[test]
+fn verify() {
+    let client = client(Algorithm::SipHash24);
+    let mut client_cookie = client.encode();
+    assert!(client.verify(&client_cookie));
+    client_cookie[0] ^= 1;
+    assert!(!client.verify(&client_cookie));
+    assert!(client != client_cookie);
+}
@@ -0,0 +1,60 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Clock, Error, FixedClock, ManualClock, Server, Version};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+
+fn decode(now: impl Clock) -> Result<(Server, usize), Error> {
+    Server::decode(
+        now,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        &VECTOR.server_cookie,
+        &[VECTOR.server_secret],
+    )
+}
+
+#[test]
+fn unix_seconds() {
+    let cookie = Server::new(
+        Version::One,
+        [0; 3],
+        VECTOR.timestamp as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        &VECTOR.server_secret,
+    );
+    assert_eq!(cookie.encode(), VECTOR.server_cookie);
+    assert!(decode(FixedClock::new(VECTOR.timestamp as i64)).is_ok());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn offset_date_time() {
+    let time = time::OffsetDateTime::from_unix_timestamp(VECTOR.timestamp as i64).unwrap();
+    assert_eq!(time.now(), VECTOR.timestamp as i64);
+    assert!(decode(time).is_ok());
+}
+
+#[test]
+fn manual_clock() {
+    let clock = ManualClock::new(VECTOR.timestamp as i64);
+    assert!(decode(&clock).is_ok());
+    clock.advance(3601);
+    assert_eq!(decode(&clock).map(|_| ()), Err(Error::Expired));
+    clock.set(VECTOR.timestamp as i64 - 301);
+    assert_eq!(decode(&clock).map(|_| ()), Err(Error::TimeTravellor));
+}
+
+#[cfg(not(feature = "no-std-net"))]
+#[test]
+fn system_clock() {
+    use dns_cookie::SystemClock;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let before = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let now = SystemClock.now();
+    assert!(before <= now && now <= before + 1);
+}
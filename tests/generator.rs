@@ -0,0 +1,72 @@
+use dns_cookie::{Algorithm, Client, ClientCookieGenerator, Secret, Version};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+const NOW: i64 = 1_559_731_985;
+const CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+const OTHER_CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 101));
+const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203));
+const IPV6_SERVER_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x53));
+const CLIENT_SECRET: Secret = Secret::new([1; 16]);
+const NEW_CLIENT_SECRET: Secret = Secret::new([2; 16]);
+
+#[test]
+fn derives_cookies_per_server() {
+    let generator = ClientCookieGenerator::new(NOW, CLIENT_SECRET);
+    let cookie = generator.cookie(Some(CLIENT_IP), SERVER_IP);
+    assert_eq!(
+        cookie,
+        Client::new(
+            Version::One,
+            Algorithm::SipHash24,
+            CLIENT_IP,
+            SERVER_IP,
+            &CLIENT_SECRET
+        )
+    );
+    assert_ne!(cookie, generator.cookie(Some(CLIENT_IP), IPV6_SERVER_IP));
+    assert_ne!(cookie, generator.cookie(Some(OTHER_CLIENT_IP), SERVER_IP));
+}
+
+#[test]
+fn unknown_client_ip() {
+    let generator = ClientCookieGenerator::new(NOW, CLIENT_SECRET);
+    let unspecified = "0.0.0.0".parse().unwrap();
+    assert_eq!(
+        generator.cookie(None, SERVER_IP),
+        generator.cookie(Some(unspecified), SERVER_IP)
+    );
+    let unspecified = "::".parse().unwrap();
+    assert_eq!(
+        generator.cookie(None, IPV6_SERVER_IP),
+        generator.cookie(Some(unspecified), IPV6_SERVER_IP)
+    );
+}
+
+#[test]
+fn refreshes_secret_on_schedule() {
+    let mut generator =
+        ClientCookieGenerator::new(NOW, CLIENT_SECRET).lifetime(Duration::from_secs(60 * 60));
+    let cookie = generator.cookie(None, SERVER_IP);
+
+    assert!(!generator.is_due(NOW + 3599));
+    assert!(!generator.refresh(NOW + 3599, || NEW_CLIENT_SECRET));
+    assert_eq!(generator.cookie(None, SERVER_IP), cookie);
+
+    assert!(generator.is_due(NOW + 3600));
+    assert!(generator.refresh(NOW + 3600, || NEW_CLIENT_SECRET));
+    assert_ne!(generator.cookie(None, SERVER_IP), cookie);
+    assert!(!generator.is_due(NOW + 7199));
+}
+
+#[test]
+fn unbounded_lifetime() {
+    for lifetime in &[Duration::MAX, Duration::from_secs(i64::MAX as u64)] {
+        let generator = ClientCookieGenerator::new(NOW, CLIENT_SECRET).lifetime(*lifetime);
+        assert!(!generator.is_due(NOW));
+        assert!(!generator.is_due(i64::MAX - 1));
+    }
+}
@@ -0,0 +1,86 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Algorithm, Client, CookieMac, Error, Secret, Server, Version};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+const CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203));
+const CLIENT_SECRET: Secret = Secret::new([1; 16]);
+
+/// Folds the message into 8 bytes, keyed with a single byte
+struct Fold(u8);
+
+impl CookieMac for Fold {
+    fn mac(&self, message: &[u8]) -> [u8; 8] {
+        let mut hash = [self.0; 8];
+        for (i, byte) in message.iter().enumerate() {
+            hash[i % 8] ^= byte;
+        }
+        hash
+    }
+}
+
+#[test]
+fn secret_is_siphash24() {
+    let server = Server::new(
+        Version::One,
+        [0; 3],
+        VECTOR.timestamp as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        &&VECTOR.server_secret,
+    );
+    assert_eq!(server.encode(), VECTOR.server_cookie);
+
+    let client = Client::new(
+        Version::One,
+        Algorithm::SipHash24,
+        CLIENT_IP,
+        SERVER_IP,
+        &CLIENT_SECRET,
+    );
+    let with_mac = Client::new_with_mac(Version::One, CLIENT_IP, SERVER_IP, &CLIENT_SECRET);
+    assert_eq!(client, with_mac);
+}
+
+#[test]
+fn custom_mac() {
+    let server = Server::new(
+        Version::One,
+        [0; 3],
+        VECTOR.timestamp as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        &Fold(0x55),
+    );
+    let server_cookie = server.encode();
+    assert_eq!(&server_cookie[..8], &VECTOR.server_cookie[..8]);
+    let mut expected = Fold(0x55).mac(&VECTOR.client_cookie);
+    for (i, byte) in [1, 0, 0, 0]
+        .iter()
+        .chain(&server_cookie[4..8])
+        .chain(&[198, 51, 100, 100])
+        .enumerate()
+    {
+        expected[i % 8] ^= byte;
+    }
+    assert_eq!(&server_cookie[8..], &expected);
+
+    let decode = |secrets: &[Fold]| {
+        Server::decode(
+            VECTOR.timestamp as i64,
+            VECTOR.client_cookie,
+            VECTOR.client_ip,
+            &server_cookie,
+            secrets,
+        )
+    };
+    assert_eq!(decode(&[Fold(0), Fold(0x55)]), Ok((server, 1)));
+    assert_eq!(decode(&[Fold(0)]), Err(Error::InvalidHash));
+
+    let client = Client::new_with_mac(Version::One, CLIENT_IP, SERVER_IP, &Fold(0));
+    assert_eq!(client.encode(), [198, 51, 100, 100, 203, 0, 113, 203]);
+}
@@ -0,0 +1,89 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Client, CookieOption, Error};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+
+fn option(len: usize) -> Vec<u8> {
+    let mut option = vec![0, 10];
+    option.extend_from_slice(&(len as u16).to_be_bytes());
+    option.resize(4 + len, 0xaa);
+    option
+}
+
+#[test]
+fn lengths() {
+    for len in 0..=48 {
+        let result = CookieOption::decode(&option(len));
+        if len == 8 || (16..=40).contains(&len) {
+            assert_eq!(result.unwrap().data().len(), len);
+        } else {
+            assert_eq!(result, Err(Error::IncorrectLength(len)));
+        }
+    }
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(
+        CookieOption::decode(&[0, 10, 0]),
+        Err(Error::IncorrectLength(3))
+    );
+    let mut wrong_code = option(8);
+    wrong_code[1] = 11;
+    assert_eq!(
+        CookieOption::decode(&wrong_code),
+        Err(Error::UnknownOption(11))
+    );
+    let mut wrong_len = option(16);
+    wrong_len[3] = 17;
+    assert_eq!(
+        CookieOption::decode(&wrong_len),
+        Err(Error::IncorrectLength(16))
+    );
+}
+
+#[test]
+fn client_only() {
+    let option = CookieOption::new(VECTOR.client_cookie, &[]).unwrap();
+    assert_eq!(option.client_cookie(), VECTOR.client_cookie);
+    assert_eq!(option.server_cookie(), None);
+    assert!(option
+        .server(
+            VECTOR.timestamp as i64,
+            VECTOR.client_ip,
+            &[VECTOR.server_secret]
+        )
+        .is_none());
+}
+
+#[test]
+fn round_trip() {
+    let mut wire = VECTOR.client_cookie.to_vec();
+    wire.extend_from_slice(&VECTOR.server_cookie);
+    let option = CookieOption::decode_data(&wire).unwrap();
+    assert_eq!(option.data(), &wire[..]);
+    assert_eq!(option.client(), Client::decode(VECTOR.client_cookie));
+    assert_eq!(option.server_cookie(), Some(&VECTOR.server_cookie[..]));
+
+    let (server, _) = option
+        .server(
+            VECTOR.timestamp as i64,
+            VECTOR.client_ip,
+            &[VECTOR.server_secret],
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        CookieOption::from_cookies(option.client(), Some(server)),
+        option
+    );
+
+    let mut buf = [0; 64];
+    assert_eq!(option.encode(&mut buf), Ok(28));
+    assert_eq!(&buf[..4], &[0, 10, 0, 24]);
+    assert_eq!(CookieOption::decode(&buf[..28]), Ok(option));
+    assert_eq!(
+        option.encode(&mut buf[..27]),
+        Err(Error::BufferTooSmall(28))
+    );
+}
@@ -0,0 +1,254 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Secret, SecretRing, Server, ServerAction, ServerPolicy, Version};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+
+fn process(policy: ServerPolicy, timestamp: u32, option: Option<&[u8]>) -> ServerAction {
+    policy.process(
+        option,
+        VECTOR.client_ip,
+        timestamp as i64,
+        &VECTOR.server_secret,
+        &[],
+    )
+}
+
+fn option(server_cookie: &[u8]) -> Vec<u8> {
+    let mut option = VECTOR.client_cookie.to_vec();
+    option.extend_from_slice(server_cookie);
+    option
+}
+
+#[test]
+fn no_option() {
+    let action = process(ServerPolicy::new(), VECTOR.timestamp, None);
+    assert_eq!(action, ServerAction::Proceed(None));
+    assert_eq!(action.rcode(), 0);
+}
+
+#[test]
+fn malformed_option() {
+    for policy in &[ServerPolicy::new(), ServerPolicy::new().enforce(true)] {
+        let action = process(*policy, VECTOR.timestamp, Some(&option(&[0; 4])));
+        assert_eq!(action, ServerAction::FormErr);
+        assert_eq!(action.rcode(), 1);
+    }
+}
+
+#[test]
+fn client_cookie_only() {
+    let expected = option(&VECTOR.server_cookie);
+    let action = process(ServerPolicy::new(), VECTOR.timestamp, Some(&option(&[])));
+    assert!(matches!(action, ServerAction::Fresh(_)));
+    assert_eq!(action.option().unwrap().data(), &expected[..]);
+
+    let action = process(
+        ServerPolicy::new().enforce(true),
+        VECTOR.timestamp,
+        Some(&option(&[])),
+    );
+    assert!(matches!(action, ServerAction::BadCookie(_)));
+    assert_eq!(action.rcode(), 23);
+    assert_eq!(action.option().unwrap().data(), &expected[..]);
+}
+
+#[test]
+fn invalid_server_cookie() {
+    let mut server_cookie = VECTOR.server_cookie;
+    server_cookie[15] ^= 1;
+    let action = process(
+        ServerPolicy::new().enforce(true),
+        VECTOR.timestamp,
+        Some(&option(&server_cookie)),
+    );
+    assert!(matches!(action, ServerAction::BadCookie(_)));
+    assert_eq!(
+        action.option().unwrap().data(),
+        &option(&VECTOR.server_cookie)[..]
+    );
+}
+
+#[test]
+fn valid_server_cookie() {
+    let action = process(
+        ServerPolicy::new().enforce(true),
+        VECTOR.timestamp + 60,
+        Some(&option(&VECTOR.server_cookie)),
+    );
+    assert_eq!(action.rcode(), 0);
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+    assert_eq!(
+        action.option().unwrap().data(),
+        &option(&VECTOR.server_cookie)[..]
+    );
+
+    let renewed = test_vectors::RENEWED_SERVER_COOKIE;
+    let action = process(
+        ServerPolicy::new(),
+        renewed.timestamp,
+        Some(&option(&VECTOR.server_cookie)),
+    );
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+    assert_eq!(
+        action.option().unwrap().data(),
+        &option(&renewed.server_cookie)[..]
+    );
+}
+
+#[test]
+fn replaces_cookie_of_older_secret() {
+    let old_secret = test_vectors::IPV6_ROLLED_OVER_SECRET.server_secret;
+    let old = Server::new(
+        Version::One,
+        [0; 3],
+        VECTOR.timestamp as i64 - 60,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        &old_secret,
+    );
+    let action = ServerPolicy::new().enforce(true).process(
+        Some(&option(&old.encode())),
+        VECTOR.client_ip,
+        VECTOR.timestamp as i64,
+        &VECTOR.server_secret,
+        &[old_secret],
+    );
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+    assert_eq!(
+        action.option().unwrap().data(),
+        &option(&VECTOR.server_cookie)[..]
+    );
+}
+
+#[test]
+fn secret_ring() {
+    let old_secret = test_vectors::IPV6_ROLLED_OVER_SECRET.server_secret;
+    let now = VECTOR.timestamp as i64;
+    let mut ring = SecretRing::new(old_secret);
+    let next_secret = Secret::new([2; 16]);
+    ring.introduce(now - 120, VECTOR.server_secret, now - 60);
+    ring.introduce(now - 30, next_secret, now + 600);
+    let cookie = |timestamp: i64, secret| {
+        Server::new(
+            Version::One,
+            [0; 3],
+            timestamp,
+            VECTOR.client_cookie,
+            VECTOR.client_ip,
+            secret,
+        )
+        .encode()
+    };
+    let process = |server_cookie: &[u8]| {
+        ServerPolicy::new().enforce(true).process_ring(
+            Some(&option(server_cookie)),
+            VECTOR.client_ip,
+            now,
+            &ring,
+        )
+    };
+
+    // Cookies of the retiring secret are replaced by one of the active secret
+    let action = process(&cookie(now - 90, &old_secret));
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+    assert_eq!(
+        action.option().unwrap().data(),
+        &option(&VECTOR.server_cookie)[..]
+    );
+
+    let active = cookie(now - 30, &VECTOR.server_secret);
+    let action = process(&active);
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+    assert_eq!(action.option().unwrap().data(), &option(&active)[..]);
+
+    // Servers that activated the pending secret early are trusted
+    let pending = cookie(now - 10, &next_secret);
+    let action = process(&pending);
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+    assert_eq!(action.option().unwrap().data(), &option(&pending)[..]);
+
+    let action = process(&cookie(now, &Secret::new([1; 16])));
+    assert!(matches!(action, ServerAction::BadCookie(_)));
+    assert_eq!(
+        action.option().unwrap().data(),
+        &option(&VECTOR.server_cookie)[..]
+    );
+}
+
+mod client {
+    use super::{option, VECTOR};
+    use dns_cookie::{ClientAction, ClientPolicy, CookieOption, BADCOOKIE};
+
+    fn query(server_cookie: &[u8]) -> CookieOption {
+        CookieOption::new(VECTOR.client_cookie, server_cookie).unwrap()
+    }
+
+    fn response(server_cookie: &[u8]) -> CookieOption {
+        CookieOption::decode_data(&option(server_cookie)).unwrap()
+    }
+
+    #[test]
+    fn accepts_matching_client_cookie() {
+        let action =
+            ClientPolicy::new().process(&query(&[]), 0, Some(&option(&VECTOR.server_cookie)), 0);
+        assert_eq!(
+            action,
+            ClientAction::Accept(Some(response(&VECTOR.server_cookie)))
+        );
+        let action = ClientPolicy::new().process(&query(&[]), 0, Some(&option(&[])), 0);
+        assert_eq!(action, ClientAction::Accept(None));
+    }
+
+    #[test]
+    fn discards_spoofed_and_malformed() {
+        let mut spoofed = option(&VECTOR.server_cookie);
+        spoofed[0] ^= 1;
+        let policy = ClientPolicy::new();
+        assert_eq!(
+            policy.process(&query(&[]), 0, Some(&spoofed), 0),
+            ClientAction::Discard
+        );
+        assert_eq!(
+            policy.process(&query(&[]), 0, Some(&option(&[0; 4])), 0),
+            ClientAction::Discard
+        );
+    }
+
+    #[test]
+    fn missing_option() {
+        let policy = ClientPolicy::new();
+        assert_eq!(
+            policy.process(&query(&[]), 0, None, 0),
+            ClientAction::Accept(None)
+        );
+        assert_eq!(
+            policy.process(&query(&VECTOR.server_cookie), 0, None, 0),
+            ClientAction::Discard
+        );
+        assert_eq!(
+            policy.process(&query(&[]), BADCOOKIE, None, 0),
+            ClientAction::Discard
+        );
+    }
+
+    #[test]
+    fn retries_badcookie() {
+        let renewed = super::test_vectors::RENEWED_SERVER_COOKIE.server_cookie;
+        let policy = ClientPolicy::new().retries(2);
+        let query = query(&VECTOR.server_cookie);
+        for retries in 0..2 {
+            assert_eq!(
+                policy.process(&query, BADCOOKIE, Some(&option(&renewed)), retries),
+                ClientAction::Retry(response(&renewed))
+            );
+        }
+        assert_eq!(
+            policy.process(&query, BADCOOKIE, Some(&option(&renewed)), 2),
+            ClientAction::GiveUp(response(&renewed))
+        );
+        assert_eq!(
+            policy.process(&query, BADCOOKIE, Some(&option(&[])), 0),
+            ClientAction::Discard
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{BadCookieRetry, Client, ClientCache, CookieOption, Retry};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr};
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+const RENEWED: Vector = test_vectors::RENEWED_SERVER_COOKIE;
+const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203));
+
+fn option(server_cookie: &[u8]) -> Vec<u8> {
+    let mut option = VECTOR.client_cookie.to_vec();
+    option.extend_from_slice(server_cookie);
+    option
+}
+
+#[test]
+fn udp_then_tcp_then_fail() {
+    let mut cache = ClientCache::<4>::new();
+    let mut retry = BadCookieRetry::new();
+    let client = Client::decode(VECTOR.client_cookie);
+
+    let first = CookieOption::decode_data(&option(&VECTOR.server_cookie)).unwrap();
+    let query = cache.option(SERVER_IP, client);
+    let action = retry.process(
+        &mut cache,
+        SERVER_IP,
+        &query,
+        Some(&option(&VECTOR.server_cookie)),
+    );
+    assert_eq!(action, Retry::Udp(first));
+    assert_eq!(cache.option(SERVER_IP, client), first);
+
+    let second = CookieOption::decode_data(&option(&RENEWED.server_cookie)).unwrap();
+    let query = cache.option(SERVER_IP, client);
+    let action = retry.process(
+        &mut cache,
+        SERVER_IP,
+        &query,
+        Some(&option(&RENEWED.server_cookie)),
+    );
+    assert_eq!(action, Retry::Tcp(second));
+    assert_eq!(cache.option(SERVER_IP, client), second);
+
+    let query = cache.option(SERVER_IP, client);
+    let action = retry.process(
+        &mut cache,
+        SERVER_IP,
+        &query,
+        Some(&option(&RENEWED.server_cookie)),
+    );
+    assert_eq!(action, Retry::Fail);
+}
+
+#[test]
+fn discards_spoofed_responses() {
+    let mut cache = ClientCache::<4>::new();
+    let mut retry = BadCookieRetry::new().udp_retries(0);
+    let client = Client::decode(test_vectors::RESERVED_SERVER_COOKIE.client_cookie);
+    let query = CookieOption::from_cookies(client, None);
+    for option in &[None, Some(option(&[])), Some(option(&VECTOR.server_cookie))] {
+        let action = retry.process(&mut cache, SERVER_IP, &query, option.as_deref());
+        assert_eq!(action, Retry::Discard);
+    }
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,87 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Server, Version};
+
+fn decode(vector: &Vector) -> Server {
+    Server::decode(
+        vector.timestamp as i64,
+        vector.client_cookie,
+        vector.client_ip,
+        &vector.server_cookie,
+        &[vector.server_secret],
+    )
+    .unwrap()
+    .0
+}
+
+#[test]
+fn new_server_cookie() {
+    for vector in &[
+        test_vectors::NEW_SERVER_COOKIE,
+        test_vectors::RENEWED_SERVER_COOKIE,
+        test_vectors::RESERVED_RENEWED_SERVER_COOKIE,
+        test_vectors::IPV6_ROLLED_OVER_SECRET,
+    ] {
+        let cookie = Server::new(
+            Version::One,
+            [0; 3],
+            vector.timestamp as i64,
+            vector.client_cookie,
+            vector.client_ip,
+            &vector.server_secret,
+        );
+        assert_eq!(cookie.encode(), vector.server_cookie);
+    }
+}
+
+#[test]
+fn decode_all() {
+    for vector in &test_vectors::ALL {
+        assert_eq!(decode(vector).encode(), vector.server_cookie);
+    }
+}
+
+#[test]
+fn renewed_server_cookie() {
+    let new = test_vectors::NEW_SERVER_COOKIE;
+    let renewed = test_vectors::RENEWED_SERVER_COOKIE;
+    let cookie = decode(&new).regenerate(renewed.timestamp as i64, &renewed.server_secret);
+    assert_eq!(cookie.encode(), renewed.server_cookie);
+}
+
+#[test]
+fn reserved_is_reset_on_renewal() {
+    let reserved = test_vectors::RESERVED_SERVER_COOKIE;
+    let renewed = test_vectors::RESERVED_RENEWED_SERVER_COOKIE;
+    let cookie = decode(&reserved).regenerate(renewed.timestamp as i64, &renewed.server_secret);
+    assert_eq!(cookie.encode(), renewed.server_cookie);
+}
+
+#[test]
+fn fresh_cookie_is_not_renewed() {
+    let vector = test_vectors::NEW_SERVER_COOKIE;
+    let cookie = decode(&vector).regenerate((vector.timestamp + 60) as i64, &vector.server_secret);
+    assert_eq!(cookie.encode(), vector.server_cookie);
+}
+
+#[test]
+fn rolled_over_secret() {
+    let vector = test_vectors::IPV6_ROLLED_OVER_SECRET;
+    let old_secret = test_vectors::NEW_SERVER_COOKIE.server_secret;
+    let old = Server::new(
+        Version::One,
+        [0; 3],
+        (vector.timestamp - 600) as i64,
+        vector.client_cookie,
+        vector.client_ip,
+        &old_secret,
+    );
+    let cookie = Server::decode(
+        vector.timestamp as i64,
+        vector.client_cookie,
+        vector.client_ip,
+        &old.encode(),
+        &[vector.server_secret, old_secret],
+    )
+    .unwrap();
+    assert_eq!(cookie, (old, 1));
+}
@@ -0,0 +1,183 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Secret, SecretRing, SecretRole, Server, Timing, Version};
+use std::time::Duration;
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+const NEXT: Secret = test_vectors::IPV6_ROLLED_OVER_SECRET.server_secret;
+
+fn cookie(timestamp: u32, secret: &Secret) -> [u8; 16] {
+    Server::new(
+        Version::One,
+        [0; 3],
+        timestamp as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        secret,
+    )
+    .encode()
+}
+
+fn decode(ring: &SecretRing, timestamp: u32, server_cookie: &[u8]) -> Option<SecretRole> {
+    ring.decode(
+        timestamp as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        server_cookie,
+    )
+    .ok()
+    .map(|(_, role)| role)
+}
+
+fn roles(ring: &SecretRing, timestamp: u32) -> Vec<(Secret, SecretRole)> {
+    ring.secrets(timestamp as i64)
+        .map(|(secret, role)| (*secret, role))
+        .collect()
+}
+
+#[test]
+fn single_secret() {
+    let ring = SecretRing::new(VECTOR.server_secret);
+    let server = ring.create(
+        VECTOR.timestamp as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+    );
+    assert_eq!(server.encode(), VECTOR.server_cookie);
+    assert_eq!(
+        decode(&ring, VECTOR.timestamp, &VECTOR.server_cookie),
+        Some(SecretRole::Active)
+    );
+}
+
+#[test]
+fn rollover() {
+    let introduction = VECTOR.timestamp;
+    let activation = introduction + 600;
+    let mut ring = SecretRing::new(VECTOR.server_secret);
+    ring.introduce(introduction as i64, NEXT, activation as i64);
+
+    // The new secret only validates cookies before its activation
+    assert_eq!(ring.secret(introduction as i64), &VECTOR.server_secret);
+    assert_eq!(
+        decode(&ring, introduction, &cookie(introduction, &NEXT)),
+        Some(SecretRole::Pending)
+    );
+    assert_eq!(
+        roles(&ring, introduction),
+        vec![
+            (VECTOR.server_secret, SecretRole::Active),
+            (NEXT, SecretRole::Pending)
+        ]
+    );
+
+    // Then replaces the old secret, which keeps validating cookies for an hour
+    assert_eq!(ring.secret(activation as i64), &NEXT);
+    let old = cookie(activation - 1, &VECTOR.server_secret);
+    assert_eq!(decode(&ring, activation, &old), Some(SecretRole::Retiring));
+    assert_eq!(
+        roles(&ring, activation + 3599),
+        vec![
+            (NEXT, SecretRole::Active),
+            (VECTOR.server_secret, SecretRole::Retiring)
+        ]
+    );
+    assert_eq!(
+        roles(&ring, activation + 3600),
+        vec![(NEXT, SecretRole::Active)]
+    );
+
+    // Rotating does not change the roles
+    for timestamp in &[activation, activation + 3600] {
+        let mut rotated = ring;
+        rotated.rotate(*timestamp as i64);
+        assert_eq!(roles(&rotated, *timestamp), roles(&ring, *timestamp));
+    }
+}
+
+#[test]
+fn renews_cookies_of_retiring_secret() {
+    let activation = VECTOR.timestamp + 1;
+    let mut ring = SecretRing::new(VECTOR.server_secret);
+    ring.introduce(VECTOR.timestamp as i64, NEXT, activation as i64);
+
+    let (server, role) = ring
+        .decode(
+            activation as i64,
+            VECTOR.client_cookie,
+            VECTOR.client_ip,
+            &VECTOR.server_cookie,
+        )
+        .unwrap();
+    assert_eq!(role, SecretRole::Retiring);
+    let renewed = ring.regenerate(activation as i64, server, role);
+    assert_eq!(renewed.encode(), cookie(activation, &NEXT));
+    assert_eq!(
+        decode(&ring, activation, &renewed.encode()),
+        Some(SecretRole::Active)
+    );
+}
+
+#[test]
+fn back_to_back_rollovers() {
+    let t0 = VECTOR.timestamp as i64;
+    let third = Secret::new([3; 16]);
+    let mut ring = SecretRing::new(VECTOR.server_secret);
+    ring.introduce(t0, NEXT, t0 + 10);
+    ring.introduce(t0 + 20, third, t0 + 30);
+    let old = cookie(VECTOR.timestamp + 9, &VECTOR.server_secret);
+
+    // The third secret waits for the first one to be retired
+    for now in &[t0 + 40, t0 + 3608] {
+        let mut rotated = ring;
+        rotated.rotate(*now);
+        assert_eq!(rotated.secret(*now), &NEXT);
+        assert_eq!(
+            decode(&rotated, *now as u32, &old),
+            Some(SecretRole::Retiring)
+        );
+        assert_eq!(
+            roles(&rotated, *now as u32),
+            vec![
+                (NEXT, SecretRole::Active),
+                (third, SecretRole::Pending),
+                (VECTOR.server_secret, SecretRole::Retiring)
+            ]
+        );
+    }
+
+    let activation = t0 + 3610;
+    for rotate in &[false, true] {
+        let mut rotated = ring;
+        if *rotate {
+            rotated.rotate(activation);
+        }
+        assert_eq!(rotated.secret(activation), &third);
+        assert_eq!(decode(&rotated, activation as u32, &old), None);
+        assert_eq!(
+            roles(&rotated, activation as u32),
+            vec![(third, SecretRole::Active), (NEXT, SecretRole::Retiring)]
+        );
+    }
+}
+
+#[test]
+fn unbounded_lifetime() {
+    let t0 = VECTOR.timestamp as i64;
+    for lifetime in &[Duration::MAX, Duration::from_secs(u64::MAX / 2)] {
+        let mut ring =
+            SecretRing::new(VECTOR.server_secret).timing(Timing::new().lifetime(*lifetime));
+        ring.introduce(t0, NEXT, t0 + 10);
+        ring.rotate(t0 + 20);
+        assert_eq!(ring.secret(t0 + 20), &NEXT);
+        assert_eq!(
+            roles(&ring, VECTOR.timestamp + 20),
+            vec![
+                (NEXT, SecretRole::Active),
+                (VECTOR.server_secret, SecretRole::Retiring)
+            ]
+        );
+        ring.introduce(t0 + 30, Secret::new([3; 16]), t0 + 40);
+        ring.rotate(i64::MAX - 1);
+        assert_eq!(ring.secret(i64::MAX - 1), &NEXT);
+    }
+}
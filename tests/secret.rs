@@ -0,0 +1,177 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{
+    Algorithm, Client, ClientCookieGenerator, ClientSecret, CookieOption, Error, Secret,
+    SecretRing, SecretRole, Server, ServerAction, ServerPolicy, ServerSecret, Version,
+};
+#[cfg(feature = "no-std-net")]
+use no_std_net::{IpAddr, Ipv4Addr};
+use std::convert::TryFrom;
+#[cfg(not(feature = "no-std-net"))]
+use std::net::{IpAddr, Ipv4Addr};
+
+const NEW: Vector = test_vectors::NEW_SERVER_COOKIE;
+const RENEWED: Vector = test_vectors::RENEWED_SERVER_COOKIE;
+const CLIENT_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 203));
+const CLIENT_SECRET: Secret = Secret::new([1; 16]);
+
+#[test]
+fn server_secret() {
+    let secret = ServerSecret::from(NEW.server_secret);
+    let cookie = Server::new(
+        Version::One,
+        [0; 3],
+        NEW.timestamp as i64,
+        NEW.client_cookie,
+        NEW.client_ip,
+        &secret,
+    );
+    assert_eq!(cookie.encode(), NEW.server_cookie);
+
+    let secrets = [ServerSecret::new(*RENEWED.server_secret.as_bytes())];
+    let (cookie, index) = Server::decode(
+        NEW.timestamp as i64,
+        NEW.client_cookie,
+        NEW.client_ip,
+        &NEW.server_cookie,
+        &secrets,
+    )
+    .unwrap();
+    assert_eq!(index, 0);
+    let cookie = cookie.regenerate(RENEWED.timestamp as i64, &secrets[0]);
+    assert_eq!(cookie.encode(), RENEWED.server_cookie);
+}
+
+#[test]
+fn client_secret() {
+    let secret = ClientSecret::from(CLIENT_SECRET);
+    assert_eq!(
+        Client::new(Version::One, Algorithm::Fnv, CLIENT_IP, SERVER_IP, &secret),
+        Client::new(
+            Version::One,
+            Algorithm::Fnv,
+            CLIENT_IP,
+            SERVER_IP,
+            &CLIENT_SECRET
+        )
+    );
+}
+
+#[test]
+fn owned_secrets_in_higher_layers() {
+    let now = NEW.timestamp as i64;
+    let mut ring = SecretRing::new(ServerSecret::from(NEW.server_secret));
+    ring.introduce(now, ServerSecret::new([1; 16]), now + 600);
+    let (_, role) = ring
+        .decode(now, NEW.client_cookie, NEW.client_ip, &NEW.server_cookie)
+        .unwrap();
+    assert_eq!(role, SecretRole::Active);
+
+    let mut data = NEW.client_cookie.to_vec();
+    data.extend_from_slice(&NEW.server_cookie);
+    let option = CookieOption::decode_data(&data).unwrap();
+    let secrets = [ServerSecret::from(NEW.server_secret)];
+    assert!(matches!(
+        option.server(now, NEW.client_ip, &secrets),
+        Some(Ok((_, 0)))
+    ));
+    let action = ServerPolicy::new().enforce(true).process(
+        Some(&data),
+        NEW.client_ip,
+        now,
+        &secrets[0],
+        &[],
+    );
+    assert!(matches!(action, ServerAction::Proceed(Some(_))));
+
+    let generator = ClientCookieGenerator::new(now, ClientSecret::from(CLIENT_SECRET));
+    let expected = ClientCookieGenerator::new(now, CLIENT_SECRET);
+    assert_eq!(
+        generator.cookie(Some(CLIENT_IP), SERVER_IP),
+        expected.cookie(Some(CLIENT_IP), SERVER_IP)
+    );
+}
+
+#[test]
+fn hidden_debug() {
+    assert_eq!(
+        format!("{:?}", ServerSecret::new([1; 16])),
+        "ServerSecret(..)"
+    );
+    assert_eq!(
+        format!("{:?}", ClientSecret::new([1; 16])),
+        "ClientSecret(..)"
+    );
+}
+
+#[test]
+fn incorrect_length() {
+    let result = ServerSecret::try_from(&[0; 15][..]).map(|_| ());
+    assert_eq!(result, Err(Error::SecretLength(15)));
+}
+
+#[test]
+fn key_file() {
+    let secret = NEW.server_secret;
+    let contents = secret.to_key_file();
+    assert_eq!(&contents[..], b"e5e973e5a6b2a43f48e7dc849e37bfcf\n");
+    let contents = std::str::from_utf8(&contents[..]).unwrap();
+    assert_eq!(Secret::from_key_file(contents), Ok(secret));
+    let upper = format!("  {}\r\n", contents.trim().to_uppercase());
+    assert_eq!(Secret::from_key_file(&upper), Ok(secret));
+    let server_secret = ServerSecret::from_key_file(contents).unwrap();
+    assert_eq!(server_secret.as_bytes(), secret.as_bytes());
+}
+
+#[test]
+fn invalid_key_file() {
+    let result = Secret::from_key_file("e5e973e5a6b2a43f48e7dc849e37bf");
+    assert_eq!(result, Err(Error::KeyFileLength(30)));
+    let result = Secret::from_key_file("e5e973e5a6b2a43f48e7dc849e37bfcf00");
+    assert_eq!(result, Err(Error::KeyFileLength(34)));
+    let result = Secret::from_key_file("e5e973e5a6b2a43f 48e7dc849e37bfcf");
+    assert_eq!(result, Err(Error::KeyFileDigit(' ')));
+    let result = Secret::from_key_file("g5e973e5a6b2a43f48e7dc849e37bfcf");
+    assert_eq!(result, Err(Error::KeyFileDigit('g')));
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn generate() {
+    use rand_core::{impls, CryptoRng, RngCore};
+
+    struct Counter(u8);
+
+    impl RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                self.0 += 1;
+                *byte = self.0;
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for Counter {}
+
+    let mut rng = Counter(0);
+    let secret = Secret::generate(&mut rng);
+    assert_eq!(
+        secret.as_bytes(),
+        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+    );
+    let secret = ServerSecret::generate(&mut rng);
+    assert_eq!(secret.as_bytes()[0], 17);
+}
@@ -0,0 +1,63 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Error, Server, Version};
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+const WRAP: i64 = 1 << 32;
+
+fn new(timestamp: i64) -> Server {
+    Server::new(
+        Version::One,
+        [0; 3],
+        timestamp,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        &VECTOR.server_secret,
+    )
+}
+
+fn decode(now: i64, server_cookie: &[u8]) -> Result<Server, Error> {
+    Server::decode(
+        now,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        server_cookie,
+        &[VECTOR.server_secret],
+    )
+    .map(|(server, _)| server)
+}
+
+#[test]
+fn timestamp_wraps() {
+    let server = new(WRAP + 1);
+    assert_eq!(&server.encode()[4..8], &[0, 0, 0, 1]);
+    assert_eq!(server.encode(), new(1).encode());
+}
+
+#[test]
+fn created_before_wrap() {
+    let server = new(WRAP - 10);
+    assert_eq!(decode(WRAP + 10, &server.encode()), Ok(server));
+    assert_eq!(decode(WRAP + 3590, &server.encode()), Ok(server));
+    assert_eq!(decode(WRAP + 3591, &server.encode()), Err(Error::Expired));
+}
+
+#[test]
+fn created_after_wrap() {
+    let server = new(WRAP + 10);
+    assert_eq!(decode(WRAP - 290, &server.encode()), Ok(server));
+    assert_eq!(
+        decode(WRAP - 291, &server.encode()),
+        Err(Error::TimeTravellor)
+    );
+    assert_eq!(decode(WRAP + 3610, &server.encode()), Ok(server));
+}
+
+#[test]
+fn regenerate_across_wrap() {
+    let server = decode(WRAP - 600, &new(WRAP - 1200).encode()).unwrap();
+    assert_eq!(server.regenerate(WRAP + 599, &VECTOR.server_secret), server);
+    assert_eq!(
+        server.regenerate(WRAP + 600, &VECTOR.server_secret),
+        new(WRAP + 600)
+    );
+}
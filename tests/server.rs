@@ -0,0 +1,132 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Error, Secret, Server, Timing};
+use std::time::Duration;
+
+const VECTOR: Vector = test_vectors::NEW_SERVER_COOKIE;
+
+fn decode(timestamp: u32, vector: &Vector, server_cookie: &[u8]) -> Result<(Server, usize), Error> {
+    Server::decode(
+        timestamp as i64,
+        vector.client_cookie,
+        vector.client_ip,
+        server_cookie,
+        &[vector.server_secret],
+    )
+}
+
+#[test]
+fn incorrect_length() {
+    let result = decode(VECTOR.timestamp, &VECTOR, &VECTOR.server_cookie[..15]);
+    assert_eq!(result, Err(Error::IncorrectLength(15)));
+}
+
+#[test]
+fn unknown_version() {
+    let mut server_cookie = VECTOR.server_cookie;
+    server_cookie[0] = 2;
+    let result = decode(VECTOR.timestamp, &VECTOR, &server_cookie);
+    assert_eq!(result, Err(Error::UnknownVersion(2)));
+}
+
+#[test]
+fn expired() {
+    let result = decode(VECTOR.timestamp + 3601, &VECTOR, &VECTOR.server_cookie);
+    assert_eq!(result, Err(Error::Expired));
+    assert!(decode(VECTOR.timestamp + 3600, &VECTOR, &VECTOR.server_cookie).is_ok());
+}
+
+#[test]
+fn time_travellor() {
+    let result = decode(VECTOR.timestamp - 301, &VECTOR, &VECTOR.server_cookie);
+    assert_eq!(result, Err(Error::TimeTravellor));
+    assert!(decode(VECTOR.timestamp - 300, &VECTOR, &VECTOR.server_cookie).is_ok());
+}
+
+#[test]
+fn other_client_ip() {
+    let vector = Vector {
+        client_ip: test_vectors::RESERVED_SERVER_COOKIE.client_ip,
+        ..VECTOR
+    };
+    let result = decode(VECTOR.timestamp, &vector, &VECTOR.server_cookie);
+    assert_eq!(result, Err(Error::InvalidHash));
+}
+
+#[test]
+fn other_secret() {
+    let vector = Vector {
+        server_secret: Secret::new([0; 16]),
+        ..VECTOR
+    };
+    let result = decode(VECTOR.timestamp, &vector, &VECTOR.server_cookie);
+    assert_eq!(result, Err(Error::InvalidHash));
+}
+
+#[test]
+fn secret_length() {
+    use std::convert::TryFrom;
+    assert_eq!(Secret::try_from(&[0; 15][..]), Err(Error::SecretLength(15)));
+    assert!(Secret::try_from(&[0; 16][..]).is_ok());
+}
+
+#[test]
+fn custom_timing() {
+    let timing = Timing::new()
+        .lifetime(Duration::from_secs(10 * 60))
+        .skew(Duration::from_secs(60))
+        .refresh(Duration::from_secs(5 * 60));
+    let decode = |timestamp: u32| {
+        Server::decode_with(
+            &timing,
+            timestamp as i64,
+            VECTOR.client_cookie,
+            VECTOR.client_ip,
+            &VECTOR.server_cookie,
+            &[VECTOR.server_secret],
+        )
+    };
+    assert_eq!(decode(VECTOR.timestamp + 601), Err(Error::Expired));
+    assert_eq!(decode(VECTOR.timestamp - 61), Err(Error::TimeTravellor));
+    let (server, _) = decode(VECTOR.timestamp + 600).unwrap();
+
+    let regenerate = |timestamp: u32| {
+        server
+            .regenerate_with(&timing, timestamp as i64, &VECTOR.server_secret)
+            .encode()
+    };
+    assert_eq!(regenerate(VECTOR.timestamp + 299), VECTOR.server_cookie);
+    assert_ne!(regenerate(VECTOR.timestamp + 300), VECTOR.server_cookie);
+}
+
+#[test]
+fn unbounded_timing() {
+    let timing = Timing::new()
+        .lifetime(Duration::MAX)
+        .skew(Duration::MAX)
+        .refresh(Duration::MAX);
+    let decode = |now: i64| {
+        Server::decode_with(
+            &timing,
+            now,
+            VECTOR.client_cookie,
+            VECTOR.client_ip,
+            &VECTOR.server_cookie,
+            &[VECTOR.server_secret],
+        )
+    };
+    let (server, _) = decode(VECTOR.timestamp as i64).unwrap();
+    assert!(decode(VECTOR.timestamp as i64 + (1 << 31)).is_ok());
+    assert!(decode(VECTOR.timestamp as i64 - (1 << 31) + 1).is_ok());
+    let regenerated = server.regenerate_with(&timing, i64::MIN, &VECTOR.server_secret);
+    assert_eq!(regenerated, server);
+}
+
+#[test]
+fn verify() {
+    let (cookie, _) = decode(VECTOR.timestamp, &VECTOR, &VECTOR.server_cookie).unwrap();
+    assert!(cookie.verify(&VECTOR.server_cookie));
+    assert!(!cookie.verify(&VECTOR.server_cookie[..15]));
+    let mut server_cookie = VECTOR.server_cookie;
+    server_cookie[15] ^= 1;
+    assert!(!cookie.verify(&server_cookie));
+}
@@ -0,0 +1,70 @@
+use dns_cookie::test_vectors::{self, Vector};
+use dns_cookie::{Error, Secret, ServerCookieRef, Timing, Version};
+
+const VECTOR: Vector = test_vectors::RESERVED_SERVER_COOKIE;
+const OTHER: Secret = Secret::new([0; 16]);
+
+fn validate(now: u32, secrets: &[Secret]) -> Result<usize, Error> {
+    ServerCookieRef::new(&VECTOR.server_cookie)?.validate(
+        &Timing::new(),
+        now as i64,
+        VECTOR.client_cookie,
+        VECTOR.client_ip,
+        secrets,
+    )
+}
+
+#[test]
+fn fields() {
+    let cookie = ServerCookieRef::new(&VECTOR.server_cookie).unwrap();
+    assert_eq!(cookie.version(), Version::One);
+    assert_eq!(cookie.reserved(), [0xab, 0xcd, 0xef]);
+    assert_eq!(cookie.timestamp(), VECTOR.timestamp);
+    assert_eq!(&cookie.hash()[..], &VECTOR.server_cookie[8..]);
+    assert_eq!(cookie.as_bytes(), &VECTOR.server_cookie);
+}
+
+#[test]
+fn pre_checks() {
+    let result = ServerCookieRef::new(&VECTOR.server_cookie[..8]);
+    assert_eq!(result, Err(Error::IncorrectLength(8)));
+    let mut server_cookie = VECTOR.server_cookie;
+    server_cookie[0] = 0;
+    assert_eq!(
+        ServerCookieRef::new(&server_cookie),
+        Err(Error::UnknownVersion(0))
+    );
+    assert_eq!(
+        validate(VECTOR.timestamp + 3601, &[OTHER]),
+        Err(Error::Expired)
+    );
+    assert_eq!(
+        validate(VECTOR.timestamp - 301, &[OTHER]),
+        Err(Error::TimeTravellor)
+    );
+}
+
+#[test]
+fn validate_and_decode() {
+    assert_eq!(
+        validate(VECTOR.timestamp, &[OTHER, VECTOR.server_secret]),
+        Ok(1)
+    );
+    assert_eq!(
+        validate(VECTOR.timestamp, &[OTHER]),
+        Err(Error::InvalidHash)
+    );
+    let now = VECTOR.timestamp as i64;
+    let (cookie, index) = ServerCookieRef::new(&VECTOR.server_cookie)
+        .unwrap()
+        .decode(
+            &Timing::new(),
+            now,
+            VECTOR.client_cookie,
+            VECTOR.client_ip,
+            &[VECTOR.server_secret],
+        )
+        .unwrap();
+    assert_eq!(index, 0);
+    assert_eq!(cookie.encode(), VECTOR.server_cookie);
+}